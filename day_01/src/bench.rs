@@ -0,0 +1,91 @@
+use std::cmp::Reverse;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::calibration::Mode;
+use crate::lexicon::{self, Lexicon};
+use crate::{prob_two, stream};
+
+/// The part two scan before the automaton: one `match_indices` per lexicon
+/// word, then sort every hit. Kept as the benchmark baseline.
+pub fn naive_calibration_value(line: &str, lexicon: &Lexicon) -> u64 {
+    let mut result_vec = Vec::<(usize, &str, u64)>::new();
+
+    lexicon.entries().iter().for_each(|(x, y)| {
+        result_vec.extend(line.match_indices(x.as_str()).map(|val| (val.0, val.1, *y)));
+    });
+    result_vec.sort_by_key(|(index, word, _)| (*index, Reverse(word.len())));
+
+    let first = result_vec.first().unwrap();
+    let last_index = result_vec.last().unwrap().0;
+    let last = result_vec
+        .iter()
+        .find(|(index, _, _)| *index == last_index)
+        .unwrap();
+
+    lexicon::leading_digit(first.2) * 10 + lexicon::trailing_digit(last.2)
+}
+
+/// xorshift64, so the synthetic file is the same on every run.
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn write_synthetic(path: &Path, line_count: usize, lexicon: &Lexicon) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    let words = lexicon.entries();
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    for _ in 0..line_count {
+        let pieces = 2 + next_random(&mut state) % 6;
+        for _ in 0..pieces {
+            let filler = next_random(&mut state) % 5;
+            for _ in 0..filler {
+                let letter = b'a' + (next_random(&mut state) % 26) as u8;
+                file.write_all(&[letter])?;
+            }
+            let (word, _) = &words[next_random(&mut state) as usize % words.len()];
+            file.write_all(word.as_bytes())?;
+        }
+        file.write_all(b"\n")?;
+    }
+    file.flush()
+}
+
+fn time<F: FnOnce() -> io::Result<u64>>(name: &str, run: F) -> io::Result<u64> {
+    let start = Instant::now();
+    let total = run()?;
+    println!("{name:>9}: {total} in {:?}", start.elapsed());
+    Ok(total)
+}
+
+/// Times the naive scan against the automaton over a generated file of
+/// `line_count` lines.
+pub fn run(line_count: usize, lexicon: &Lexicon) -> io::Result<()> {
+    let path: PathBuf = std::env::temp_dir().join(format!("day_01_bench_{line_count}.txt"));
+    write_synthetic(&path, line_count, lexicon)?;
+    println!("{line_count} lines in {}", path.display());
+
+    let naive = time("naive", || {
+        stream::sum_lines(BufReader::new(File::open(&path)?), |line| {
+            naive_calibration_value(line, lexicon)
+        })
+    })?;
+    let automaton = time("automaton", || {
+        let reader = BufReader::new(File::open(&path)?);
+        prob_two::problem_two_reader(reader, lexicon, Mode::Strict)
+            .map(|calibration| calibration.total)
+            .map_err(io::Error::other)
+    })?;
+
+    std::fs::remove_file(&path)?;
+    if naive != automaton {
+        return Err(io::Error::other("naive and automaton totals differ"));
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::stream;
+
+/// What to do with a line that has no calibration value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Abort on the first bad line.
+    Strict,
+    /// Skip bad lines and count them.
+    Lenient,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    NoDigit,
+    NonAsciiDigit(char),
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::NoDigit => write!(f, "no digit found"),
+            Reason::NonAsciiDigit(c) => write!(f, "rejected non-ASCII numeric character {c:?}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CalibrationError {
+    Io(io::Error),
+    Line {
+        line_number: usize,
+        line: String,
+        reason: Reason,
+    },
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalibrationError::Io(err) => write!(f, "{err}"),
+            CalibrationError::Line {
+                line_number,
+                line,
+                reason,
+            } => write!(f, "line {line_number}: {reason}: {line:?}"),
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+impl From<io::Error> for CalibrationError {
+    fn from(err: io::Error) -> CalibrationError {
+        CalibrationError::Io(err)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    pub total: u64,
+    pub skipped: usize,
+}
+
+impl Calibration {
+    fn add(
+        &mut self,
+        line_number: usize,
+        line: &str,
+        value: Result<u64, Reason>,
+        mode: Mode,
+    ) -> Result<(), CalibrationError> {
+        match (value, mode) {
+            (Ok(value), _) => self.total += value,
+            (Err(_), Mode::Lenient) => self.skipped += 1,
+            (Err(reason), Mode::Strict) => {
+                return Err(CalibrationError::Line {
+                    line_number,
+                    line: line.to_string(),
+                    reason,
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Totals `value` over in-memory lines, numbered from 1. Empty lines are
+/// skipped without counting as bad.
+pub fn calibrate_lines<'a, I, F>(
+    lines: I,
+    mode: Mode,
+    mut value: F,
+) -> Result<Calibration, CalibrationError>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: FnMut(&str) -> Result<u64, Reason>,
+{
+    let mut calibration = Calibration::default();
+
+    for (index, line) in lines.into_iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        calibration.add(index + 1, line, value(line), mode)?;
+    }
+    Ok(calibration)
+}
+
+/// Totals `value` over every line of `reader` without holding more than one
+/// line in memory.
+pub fn calibrate_reader<R, F>(
+    reader: R,
+    mode: Mode,
+    mut value: F,
+) -> Result<Calibration, CalibrationError>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<u64, Reason>,
+{
+    let mut calibration = Calibration::default();
+
+    stream::for_each_line(reader, |line_number, line| {
+        calibration.add(line_number, line, value(line), mode)
+    })?;
+    Ok(calibration)
+}
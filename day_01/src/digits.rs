@@ -0,0 +1,82 @@
+use crate::calibration::Reason;
+
+/// First code point of every run of ten Unicode decimal digits (category
+/// Nd, Unicode 17.0). Each run holds the values 0 to 9 in order.
+const DECIMAL_ZEROS: [u32; 77] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950,
+    0x1FBF0,
+];
+
+/// Which characters part one reads as digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigitPolicy {
+    /// Only `0`-`9`; every other character is ignored.
+    Ascii,
+    /// Any Unicode decimal digit, mapped to its value. Other numeric
+    /// characters such as '½' or 'Ⅻ' are ignored.
+    Unicode,
+    /// Only `0`-`9`; a line holding any other numeric character is bad.
+    #[default]
+    Reject,
+}
+
+impl DigitPolicy {
+    /// The digit value of `c`, `None` when `c` is not a digit under this
+    /// policy, or the reason the whole line has to be rejected.
+    pub fn digit(self, c: char) -> Result<Option<u64>, Reason> {
+        if c.is_ascii_digit() {
+            return Ok(c.to_digit(10).map(u64::from));
+        }
+        match self {
+            DigitPolicy::Ascii => Ok(None),
+            DigitPolicy::Unicode => Ok(decimal_value(c)),
+            DigitPolicy::Reject if c.is_numeric() => Err(Reason::NonAsciiDigit(c)),
+            DigitPolicy::Reject => Ok(None),
+        }
+    }
+}
+
+fn decimal_value(c: char) -> Option<u64> {
+    let code = c as u32;
+    let index = DECIMAL_ZEROS.partition_point(|zero| *zero <= code);
+
+    let zero = DECIMAL_ZEROS[index.checked_sub(1)?];
+    (code - zero < 10).then(|| u64::from(code - zero))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digits::*;
+
+    #[test]
+    fn decimal_runs_are_numeric() {
+        for zero in DECIMAL_ZEROS {
+            for value in 0..10 {
+                let c = char::from_u32(zero + value).unwrap();
+                assert!(c.is_numeric(), "{c:?}");
+                assert_eq!(decimal_value(c), Some(u64::from(value)));
+            }
+        }
+    }
+
+    #[test]
+    fn mixed_scripts() {
+        let line = "a٣b½c7Ⅻ८";
+        let read = |policy: DigitPolicy| {
+            line.chars()
+                .map(|c| policy.digit(c))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|digits| digits.into_iter().flatten().collect::<Vec<u64>>())
+        };
+
+        assert_eq!(read(DigitPolicy::Ascii), Ok(vec![7]));
+        assert_eq!(read(DigitPolicy::Unicode), Ok(vec![3, 7, 8]));
+        assert_eq!(read(DigitPolicy::Reject), Err(Reason::NonAsciiDigit('٣')));
+    }
+}
@@ -0,0 +1,248 @@
+use std::io::{self, BufRead, Write};
+
+use crate::calibration::Reason;
+use crate::digits::DigitPolicy;
+use crate::lexicon;
+use crate::matcher::Matcher;
+use crate::stream;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+}
+
+/// A recognised digit or digit word and the byte offset it starts at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub offset: usize,
+    pub text: &'a str,
+    pub value: u64,
+}
+
+/// Everything a solver saw on one line and the value it produced from it.
+#[derive(Debug)]
+pub struct Explanation<'a> {
+    pub line_number: usize,
+    pub line: &'a str,
+    pub tokens: Vec<Token<'a>>,
+    pub first: Option<Token<'a>>,
+    pub last: Option<Token<'a>>,
+    pub rejected: Option<Reason>,
+}
+
+pub fn explain_one(line_number: usize, line: &str, policy: DigitPolicy) -> Explanation<'_> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut rejected: Option<Reason> = None;
+
+    for (offset, c) in line.char_indices() {
+        match policy.digit(c) {
+            Ok(Some(value)) => tokens.push(Token {
+                offset,
+                text: &line[offset..offset + c.len_utf8()],
+                value,
+            }),
+            Ok(None) => {}
+            Err(reason) => {
+                rejected.get_or_insert(reason);
+            }
+        }
+    }
+
+    Explanation {
+        line_number,
+        line,
+        first: tokens.first().copied(),
+        last: tokens.last().copied(),
+        tokens,
+        rejected,
+    }
+}
+
+pub fn explain_two<'a>(line_number: usize, line: &'a str, matcher: &Matcher) -> Explanation<'a> {
+    let token = |start: usize, len: usize, value: u64| Token {
+        offset: start,
+        text: &line[start..start + len],
+        value,
+    };
+    let mut tokens: Vec<Token> = matcher
+        .find_all(line)
+        .into_iter()
+        .map(|found| token(found.start, found.len, found.value))
+        .collect();
+    tokens.sort_by_key(|token| (token.offset, std::cmp::Reverse(token.text.len())));
+
+    Explanation {
+        line_number,
+        line,
+        tokens,
+        first: matcher.first(line).map(|m| token(m.start, m.len, m.value)),
+        last: matcher.last(line).map(|m| token(m.start, m.len, m.value)),
+        rejected: None,
+    }
+}
+
+impl Explanation<'_> {
+    pub fn value(&self) -> Option<u64> {
+        if self.rejected.is_some() {
+            return None;
+        }
+        let first = lexicon::leading_digit(self.first?.value);
+        let last = lexicon::trailing_digit(self.last?.value);
+        Some(first * 10 + last)
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W, format: Format) -> io::Result<()> {
+        match format {
+            Format::Human => self.write_human(out),
+            Format::Json => self.write_json(out),
+        }
+    }
+
+    fn write_human<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let show = |token: Option<Token>| match token {
+            Some(token) => format!("{:?}@{}={}", token.text, token.offset, token.value),
+            None => "-".to_string(),
+        };
+
+        writeln!(out, "line {}: {:?}", self.line_number, self.line)?;
+        write!(out, "  tokens:")?;
+        for token in &self.tokens {
+            write!(out, " {}", show(Some(*token)))?;
+        }
+        writeln!(out)?;
+        writeln!(
+            out,
+            "  first: {}  last: {}  value: {}",
+            show(self.first),
+            show(self.last),
+            self.value()
+                .map_or("-".to_string(), |value| value.to_string())
+        )?;
+        if let Some(reason) = &self.rejected {
+            writeln!(out, "  rejected: {reason}")?;
+        }
+        Ok(())
+    }
+
+    fn write_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let show = |token: Option<Token>| match token {
+            Some(token) => format!(
+                "{{\"offset\":{},\"text\":{},\"value\":{}}}",
+                token.offset,
+                json_string(token.text),
+                token.value
+            ),
+            None => "null".to_string(),
+        };
+        let tokens: Vec<String> = self.tokens.iter().map(|token| show(Some(*token))).collect();
+
+        writeln!(
+            out,
+            "{{\"line\":{},\"text\":{},\"tokens\":[{}],\"first\":{},\"last\":{},\"value\":{},\"rejected\":{}}}",
+            self.line_number,
+            json_string(self.line),
+            tokens.join(","),
+            show(self.first),
+            show(self.last),
+            self.value()
+                .map_or("null".to_string(), |value| value.to_string()),
+            self.rejected
+                .as_ref()
+                .map_or("null".to_string(), |reason| json_string(&reason.to_string()))
+        )
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Writes an explanation for every non-empty line of `reader` to `out` and
+/// returns the total of the lines that produced a value.
+pub fn run<R, W, F>(reader: R, out: &mut W, format: Format, explain: F) -> io::Result<u64>
+where
+    R: BufRead,
+    W: Write,
+    F: for<'a> Fn(usize, &'a str) -> Explanation<'a>,
+{
+    let mut count: u64 = 0;
+
+    stream::for_each_line(reader, |line_number, line| {
+        let explanation = explain(line_number, line);
+        count += explanation.value().unwrap_or(0);
+        explanation.write(out, format)
+    })?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::explain::*;
+    use crate::lexicon::Lexicon;
+
+    #[test]
+    fn part_two_lists_overlapping_tokens() {
+        let matcher = Matcher::new(&Lexicon::english());
+        let explanation = explain_two(1, "xtwone3", &matcher);
+        let tokens: Vec<(usize, &str)> = explanation
+            .tokens
+            .iter()
+            .map(|token| (token.offset, token.text))
+            .collect();
+
+        assert_eq!(tokens, [(1, "two"), (3, "one"), (6, "3")]);
+        assert_eq!(explanation.first.unwrap().text, "two");
+        assert_eq!(explanation.last.unwrap().text, "3");
+        assert_eq!(explanation.value(), Some(23));
+    }
+
+    #[test]
+    fn json_lines_output() {
+        let file = "a\"1b2\n\nnone\n";
+        let mut out = Vec::new();
+        let total = run(
+            file.as_bytes(),
+            &mut out,
+            Format::Json,
+            |line_number, line| explain_one(line_number, line, DigitPolicy::Reject),
+        )
+        .unwrap();
+
+        assert_eq!(total, 12);
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"line":1,"text":"a\"1b2","tokens":[{"offset":2,"text":"1","value":1},{"offset":4,"text":"2","value":2}],"first":{"offset":2,"text":"1","value":1},"last":{"offset":4,"text":"2","value":2},"value":12,"rejected":null}"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"{"line":3,"text":"none","tokens":[],"first":null,"last":null,"value":null,"rejected":null}"#
+        );
+    }
+
+    #[test]
+    fn part_one_shows_rejected_digit() {
+        let explanation = explain_one(4, "1٣2", DigitPolicy::Reject);
+
+        assert_eq!(explanation.tokens.len(), 2);
+        assert_eq!(explanation.rejected, Some(Reason::NonAsciiDigit('٣')));
+        assert_eq!(explanation.value(), None);
+        assert_eq!(
+            explain_one(4, "1٣2", DigitPolicy::Unicode).value(),
+            Some(12)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[rustfmt::skip]
+const ENGLISH: [(&str, u64); 9] = [
+    ("one",   1),
+    ("two",   2),
+    ("three", 3),
+    ("four",  4),
+    ("five",  5),
+    ("six",   6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine",  9),
+];
+
+#[derive(Debug)]
+pub enum LexiconError {
+    Io(io::Error),
+    Parse { line_number: usize, line: String },
+}
+
+impl fmt::Display for LexiconError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LexiconError::Io(err) => write!(f, "could not read lexicon: {err}"),
+            LexiconError::Parse { line_number, line } => write!(
+                f,
+                "lexicon line {line_number}: expected `<word> <value>`, found {line:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LexiconError {}
+
+/// The words recognised as digits by part two, together with their values.
+///
+/// The ASCII digits `1`..`9` are always part of a lexicon. A word may stand
+/// for a multi-digit value ("twelve" = 12): when it is the first match on a
+/// line it contributes its leading digit, when it is the last match its
+/// trailing digit.
+#[derive(Debug, Clone)]
+pub struct Lexicon {
+    entries: Vec<(String, u64)>,
+}
+
+impl Default for Lexicon {
+    fn default() -> Lexicon {
+        Lexicon::english()
+    }
+}
+
+impl Lexicon {
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, u64)>) -> Lexicon {
+        let mut entries: Vec<(String, u64)> = words
+            .into_iter()
+            .map(|(word, value)| (word.into(), value))
+            .filter(|(word, _)| !word.is_empty())
+            .collect();
+        entries.extend((1..=9).map(|digit| (digit.to_string(), digit)));
+
+        Lexicon { entries }
+    }
+
+    pub fn english() -> Lexicon {
+        Lexicon::new(ENGLISH)
+    }
+
+    /// Parses one `<word> <value>` pair per line. Blank lines and lines
+    /// starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Lexicon, LexiconError> {
+        let mut words: Vec<(&str, u64)> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parse_error = || LexiconError::Parse {
+                line_number: index + 1,
+                line: line.to_string(),
+            };
+            let mut split = line.split_whitespace();
+            let (word, value) = match (split.next(), split.next(), split.next()) {
+                (Some(word), Some(value), None) => (word, value),
+                _ => return Err(parse_error()),
+            };
+            words.push((word, value.parse::<u64>().map_err(|_| parse_error())?));
+        }
+        Ok(Lexicon::new(words))
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Lexicon, LexiconError> {
+        let text = fs::read_to_string(path).map_err(LexiconError::Io)?;
+        Lexicon::parse(&text)
+    }
+
+    pub fn entries(&self) -> &[(String, u64)] {
+        &self.entries
+    }
+}
+
+pub fn leading_digit(mut value: u64) -> u64 {
+    while value >= 10 {
+        value /= 10;
+    }
+    value
+}
+
+pub fn trailing_digit(value: u64) -> u64 {
+    value % 10
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexicon::*;
+
+    #[test]
+    fn parse_skips_comments_and_keeps_digits() {
+        let lexicon = Lexicon::parse("# german\neins 1\n\nzwei 2\n").unwrap();
+        let words: Vec<&str> = lexicon.entries().iter().map(|(w, _)| w.as_str()).collect();
+
+        assert_eq!(&words[..3], ["eins", "zwei", "1"]);
+        assert_eq!(words.len(), 11);
+    }
+
+    #[test]
+    fn parse_reports_line_number() {
+        match Lexicon::parse("un 1\ndeux\n") {
+            Err(LexiconError::Parse { line_number, .. }) => assert_eq!(line_number, 2),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn multi_digit_values() {
+        assert_eq!(leading_digit(12), 1);
+        assert_eq!(trailing_digit(12), 2);
+        assert_eq!(leading_digit(7), 7);
+    }
+}
@@ -1,13 +1,167 @@
-mod prob_one; 
+mod bench;
+mod calibration;
+mod digits;
+mod explain;
+mod lexicon;
+mod matcher;
+mod prob_one;
 mod prob_two;
+mod stream;
 
-fn main() {
-    let parts = include_str!("../input/prob_one_input.txt").split("\n").collect::<Vec<&str>>();
-    println!("{:}", prob_one::problem_one(parts));
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+use calibration::{Calibration, CalibrationError, Mode};
+use digits::DigitPolicy;
+use explain::Format;
+use lexicon::Lexicon;
+use matcher::Matcher;
+
+const USAGE: &str =
+    "usage: day_01 [--lexicon FILE] [--lenient] [--digits ascii|unicode|reject] [--explain [--json]] [one|two] [PATH|-]
+       day_01 [--lexicon FILE] bench [LINES]";
+
+struct Args {
+    part: Option<String>,
+    path: Option<String>,
+    lexicon: Option<String>,
+    explain: Option<Format>,
+    mode: Mode,
+    digits: DigitPolicy,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut args = Args {
+        part: None,
+        path: None,
+        lexicon: None,
+        explain: None,
+        mode: Mode::Strict,
+        digits: DigitPolicy::default(),
+    };
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--lexicon" => args.lexicon = Some(iter.next()?),
+            "--explain" => {
+                args.explain.get_or_insert(Format::Human);
+            }
+            "--lenient" => args.mode = Mode::Lenient,
+            "--digits" => {
+                args.digits = match iter.next()?.as_str() {
+                    "ascii" => DigitPolicy::Ascii,
+                    "unicode" => DigitPolicy::Unicode,
+                    "reject" => DigitPolicy::Reject,
+                    _ => return None,
+                }
+            }
+            "--json" => args.explain = Some(Format::Json),
+            "one" | "two" | "bench" if args.part.is_none() => args.part = Some(arg),
+            _ if args.part.is_some() && args.path.is_none() => args.path = Some(arg),
+            _ => return None,
+        }
+    }
+    Some(args)
+}
+
+fn open_input(path: Option<&str>) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        None | Some("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+fn fail(err: impl std::fmt::Display) -> ! {
+    eprintln!("day_01: {err}");
+    process::exit(1);
+}
+
+fn run_explain(
+    args: &Args,
+    reader: Box<dyn BufRead>,
+    format: Format,
+    lexicon: &Lexicon,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    let total = if args.part.as_deref() == Some("one") {
+        explain::run(reader, &mut out, format, |line_number, line| {
+            explain::explain_one(line_number, line, args.digits)
+        })?
+    } else {
+        let matcher = Matcher::new(lexicon);
+        explain::run(reader, &mut out, format, |line_number, line| {
+            explain::explain_two(line_number, line, &matcher)
+        })?
+    };
+
+    if format == Format::Human {
+        writeln!(out, "total: {total}")?;
+    }
+    out.flush()
+}
+
+fn report(calibration: Calibration) {
+    println!("{:}", calibration.total);
+    if calibration.skipped > 0 {
+        eprintln!("day_01: skipped {} bad lines", calibration.skipped);
+    }
+}
+
+fn run_bundled(args: &Args, lexicon: &Lexicon) -> Result<(), CalibrationError> {
+    let parts = include_str!("../input/prob_one_input.txt")
+        .split('\n')
+        .collect::<Vec<&str>>();
+    report(prob_one::problem_one(parts, args.digits, args.mode)?);
 
     println!("\n\n");
 
+    let parts_2 = include_str!("../input/prob_two_input.txt")
+        .split('\n')
+        .collect::<Vec<&str>>();
+    report(prob_two::problem_two(parts_2, lexicon, args.mode)?);
+    Ok(())
+}
+
+fn main() {
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    };
+
+    let lexicon = match &args.lexicon {
+        Some(path) => Lexicon::from_file(path).unwrap_or_else(|err| fail(err)),
+        None => Lexicon::english(),
+    };
+
+    let calibration = match args.part.as_deref() {
+        None => return run_bundled(&args, &lexicon).unwrap_or_else(|err| fail(err)),
+        Some("bench") => {
+            let lines = match args.path.as_deref().map(str::parse::<usize>) {
+                None => 1_000_000,
+                Some(Ok(lines)) => lines,
+                Some(Err(err)) => fail(err),
+            };
+            return bench::run(lines, &lexicon).unwrap_or_else(|err| fail(err));
+        }
+        Some(_) if args.explain.is_some() => {
+            let format = args.explain.unwrap();
+            return open_input(args.path.as_deref())
+                .and_then(|reader| run_explain(&args, reader, format, &lexicon))
+                .unwrap_or_else(|err| fail(err));
+        }
+        Some(part) => open_input(args.path.as_deref())
+            .map_err(CalibrationError::Io)
+            .and_then(|reader| match part {
+                "one" => prob_one::problem_one_reader(reader, args.digits, args.mode),
+                _ => prob_two::problem_two_reader(reader, &lexicon, args.mode),
+            }),
+    };
 
-    let parts_2 = include_str!("../input/prob_two_input.txt").split("\n").collect::<Vec<&str>>();
-    println!("{:}", prob_two::problem_two(parts_2));
+    match calibration {
+        Ok(calibration) => report(calibration),
+        Err(err) => fail(err),
+    }
 }
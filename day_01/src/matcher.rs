@@ -0,0 +1,185 @@
+use crate::lexicon::Lexicon;
+
+const ROOT: usize = 0;
+
+/// A word found on a line: its byte range start, length and lexicon value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub len: usize,
+    pub value: u64,
+}
+
+/// Aho-Corasick automaton with every transition resolved up front, so each
+/// input byte costs a single table lookup.
+///
+/// `output` holds the longest word ending in a state: every word ending there
+/// ends at the same byte, so the longest one is also the one that starts
+/// furthest to the left, which is all the first/last search needs. The
+/// shorter ones are reached through `dictionary` when every match is wanted.
+#[derive(Debug)]
+struct Automaton {
+    next: Vec<[u32; 256]>,
+    output: Vec<Option<(usize, u64)>>,
+    terminal: Vec<Option<(usize, u64)>>,
+    dictionary: Vec<usize>,
+}
+
+impl Automaton {
+    fn new<'a>(words: impl Iterator<Item = (&'a [u8], u64)>) -> Automaton {
+        let mut next: Vec<[u32; 256]> = vec![[u32::MAX; 256]];
+        let mut terminal: Vec<Option<(usize, u64)>> = vec![None];
+
+        for (word, value) in words {
+            let mut state = ROOT;
+            for byte in word {
+                if next[state][*byte as usize] == u32::MAX {
+                    next[state][*byte as usize] = next.len() as u32;
+                    next.push([u32::MAX; 256]);
+                    terminal.push(None);
+                }
+                state = next[state][*byte as usize] as usize;
+            }
+            // The first entry of a repeated word wins, as in the lexicon order.
+            terminal[state].get_or_insert((word.len(), value));
+        }
+
+        let mut fail = vec![ROOT; next.len()];
+        let mut output = terminal.clone();
+        let mut dictionary = vec![ROOT; next.len()];
+        let mut queue = std::collections::VecDeque::new();
+
+        for slot in next[ROOT].iter_mut() {
+            match *slot {
+                u32::MAX => *slot = ROOT as u32,
+                child => queue.push_back(child as usize),
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let suffix = fail[state];
+            if output[state].is_none() {
+                output[state] = output[suffix];
+            }
+            dictionary[state] = match terminal[suffix] {
+                Some(_) => suffix,
+                None => dictionary[suffix],
+            };
+            let fallback = next[fail[state]];
+            for (slot, fallback) in next[state].iter_mut().zip(fallback) {
+                match *slot {
+                    u32::MAX => *slot = fallback,
+                    child => {
+                        fail[child as usize] = fallback as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+
+        Automaton {
+            next,
+            output,
+            terminal,
+            dictionary,
+        }
+    }
+
+    fn step(&self, state: usize, byte: u8) -> usize {
+        self.next[state][byte as usize] as usize
+    }
+}
+
+/// Finds lexicon words on a line in a single pass from either end.
+#[derive(Debug)]
+pub struct Matcher {
+    forward: Automaton,
+    backward: Automaton,
+    longest: usize,
+}
+
+impl Matcher {
+    pub fn new(lexicon: &Lexicon) -> Matcher {
+        let entries = lexicon.entries();
+        let reversed: Vec<(Vec<u8>, u64)> = entries
+            .iter()
+            .map(|(word, value)| (word.bytes().rev().collect(), *value))
+            .collect();
+
+        Matcher {
+            forward: Automaton::new(entries.iter().map(|(w, v)| (w.as_bytes(), *v))),
+            backward: Automaton::new(reversed.iter().map(|(w, v)| (w.as_slice(), *v))),
+            longest: entries
+                .iter()
+                .map(|(word, _)| word.len())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// The match starting furthest to the left, the longer one on a tie.
+    ///
+    /// Scanning stops as soon as no later match could start at or before
+    /// the best start found so far.
+    pub fn first(&self, line: &str) -> Option<Match> {
+        let mut state = ROOT;
+        let mut best: Option<Match> = None;
+
+        for (index, byte) in line.bytes().enumerate() {
+            if let Some(found) = best {
+                if index + 1 > found.start + self.longest {
+                    break;
+                }
+            }
+
+            state = self.forward.step(state, byte);
+            if let Some((len, value)) = self.forward.output[state] {
+                let start = index + 1 - len;
+                if best.is_none_or(|found| {
+                    start < found.start || (start == found.start && len > found.len)
+                }) {
+                    best = Some(Match { start, len, value });
+                }
+            }
+        }
+        best
+    }
+
+    /// The match starting furthest to the right, the longer one on a tie.
+    ///
+    /// Every word ending at the same byte of the reversed line starts at the
+    /// same byte of the original, so the first hit is already the answer.
+    pub fn last(&self, line: &str) -> Option<Match> {
+        let mut state = ROOT;
+
+        for (index, byte) in line.bytes().rev().enumerate() {
+            state = self.backward.step(state, byte);
+            if let Some((len, value)) = self.backward.output[state] {
+                let start = line.len() - index - 1;
+                return Some(Match { start, len, value });
+            }
+        }
+        None
+    }
+
+    /// Every match on the line, ordered by end and, for a shared end, from
+    /// the longest word to the shortest.
+    pub fn find_all(&self, line: &str) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut state = ROOT;
+
+        for (index, byte) in line.bytes().enumerate() {
+            state = self.forward.step(state, byte);
+
+            let mut found = state;
+            while found != ROOT {
+                if let Some((len, value)) = self.forward.terminal[found] {
+                    let start = index + 1 - len;
+                    matches.push(Match { start, len, value });
+                }
+                found = self.forward.dictionary[found];
+            }
+        }
+        matches
+    }
+}
@@ -1,20 +1,99 @@
-pub fn problem_one(file_vec: Vec<&str>) -> u64 {
-    let mut count: u64 = 0;
+use std::io::BufRead;
 
-    for line in file_vec {
+use crate::calibration::{self, Calibration, CalibrationError, Mode, Reason};
+use crate::digits::DigitPolicy;
 
-        if line.len() == 0 {
-            continue;
+pub fn calibration_value(line: &str, policy: DigitPolicy) -> Result<u64, Reason> {
+    let mut first: Option<u64> = None;
+    let mut last: Option<u64> = None;
+
+    for c in line.chars() {
+        if let Some(digit) = policy.digit(c)? {
+            first.get_or_insert(digit);
+            last = Some(digit);
         }
+    }
+
+    match (first, last) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(Reason::NoDigit),
+    }
+}
+
+pub fn problem_one(
+    file_vec: Vec<&str>,
+    policy: DigitPolicy,
+    mode: Mode,
+) -> Result<Calibration, CalibrationError> {
+    calibration::calibrate_lines(file_vec, mode, |line| calibration_value(line, policy))
+}
+
+pub fn problem_one_reader<R: BufRead>(
+    reader: R,
+    policy: DigitPolicy,
+    mode: Mode,
+) -> Result<Calibration, CalibrationError> {
+    calibration::calibrate_reader(reader, mode, |line| calibration_value(line, policy))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prob_one::*;
+
+    #[test]
+    fn strict_reports_line_number() {
+        let file = "1abc2\n\nnothing\n3x\n";
+
+        match problem_one_reader(file.as_bytes(), DigitPolicy::Ascii, Mode::Strict) {
+            Err(CalibrationError::Line {
+                line_number, line, ..
+            }) => {
+                assert_eq!(line_number, 3);
+                assert_eq!(line, "nothing");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lenient_skips_and_counts() {
+        let file = vec!["1abc2", "nothing", "a½b", "3x"];
+        let calibration = problem_one(file, DigitPolicy::Reject, Mode::Lenient).unwrap();
+
+        assert_eq!(
+            calibration,
+            Calibration {
+                total: 45,
+                skipped: 2
+            }
+        );
+    }
 
-        let numerical_vector: Vec<_> = line.chars()
-                         .collect::<Vec<_>>()
-                         .iter()
-                         .filter(|x| x.is_numeric())
-                         .map(|x| x.to_digit(10).unwrap() as u64)
-                         .collect::<Vec<_>>();
+    #[test]
+    fn digit_policies_on_mixed_scripts() {
+        let file = vec!["٤x1", "a½b9", "۲٥", "x７y"];
+        let total = |policy| problem_one(file.clone(), policy, Mode::Lenient).unwrap();
 
-        count += numerical_vector[0] * 10 + numerical_vector[numerical_vector.len() - 1];
+        assert_eq!(
+            total(DigitPolicy::Ascii),
+            Calibration {
+                total: 110,
+                skipped: 2
+            }
+        );
+        assert_eq!(
+            total(DigitPolicy::Unicode),
+            Calibration {
+                total: 41 + 99 + 25 + 77,
+                skipped: 0
+            }
+        );
+        assert_eq!(
+            total(DigitPolicy::Reject),
+            Calibration {
+                total: 0,
+                skipped: 4
+            }
+        );
     }
-    count
 }
@@ -1,50 +1,123 @@
-const NUMBER_MAP: [(&str, i32); 18] = [ 
-    ("one",   1),
-    ("two",   2),
-    ("three", 3),
-    ("four",  4),
-    ("five",  5),
-    ("six",   6),
-    ("seven", 7),
-    ("eight", 8),
-    ("nine",  9),
-    ("1",     1),
-    ("2",     2),
-    ("3",     3),
-    ("4",     4),
-    ("5",     5),
-    ("6",     6),
-    ("7",     7),
-    ("8",     8),
-    ("9",     9),
-];
-
-pub fn problem_two(file_vec: Vec<&str>) -> u32 {
-
-    let mut count: u32 = 0;
-
-    for line in file_vec {
-
-        if line.len() == 0 {
-            continue;
-        }
+use std::io::BufRead;
+
+use crate::calibration::{self, Calibration, CalibrationError, Mode, Reason};
+use crate::lexicon::{self, Lexicon};
+use crate::matcher::Matcher;
+
+/// Words may overlap ("twone" gives 2 and 1). When two words start at the
+/// same byte the longer one wins, so "sixty" beats "six".
+pub fn calibration_value(line: &str, matcher: &Matcher) -> Result<u64, Reason> {
+    let first = matcher.first(line).ok_or(Reason::NoDigit)?;
+    let last = matcher.last(line).ok_or(Reason::NoDigit)?;
+
+    Ok(lexicon::leading_digit(first.value) * 10 + lexicon::trailing_digit(last.value))
+}
+
+pub fn problem_two(
+    file_vec: Vec<&str>,
+    lexicon: &Lexicon,
+    mode: Mode,
+) -> Result<Calibration, CalibrationError> {
+    let matcher = Matcher::new(lexicon);
+
+    calibration::calibrate_lines(file_vec, mode, |line| calibration_value(line, &matcher))
+}
+
+pub fn problem_two_reader<R: BufRead>(
+    reader: R,
+    lexicon: &Lexicon,
+    mode: Mode,
+) -> Result<Calibration, CalibrationError> {
+    let matcher = Matcher::new(lexicon);
+
+    calibration::calibrate_reader(reader, mode, |line| calibration_value(line, &matcher))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prob_two::*;
+
+    #[test]
+    fn reader_matches_bundled_input() {
+        let file = include_str!("../input/prob_two_input.txt");
+        let parts = file.split('\n').collect::<Vec<&str>>();
+
+        let lexicon = Lexicon::english();
+
+        assert_eq!(
+            problem_two_reader(file.as_bytes(), &lexicon, Mode::Strict).unwrap(),
+            problem_two(parts, &lexicon, Mode::Strict).unwrap()
+        );
+    }
 
-        let mut result_vec = Vec::<(usize, &str, u32)>::new();
-        
-
-        let numerical_vector: String = line.chars().into_iter().collect();
-        NUMBER_MAP.iter().for_each(|(x, y)| {
-                result_vec.append(
-                                    &mut numerical_vector
-                                    .match_indices(*x)
-                                    .map(|val| (val.0, val.1, *y as u32))
-                                    .collect()
-                                 );
+    #[test]
+    fn reader_handles_crlf() {
+        let file = include_str!("../input/input_sample_two.txt").replace('\n', "\r\n");
+
+        let calibration = problem_two_reader(file.as_bytes(), &Lexicon::english(), Mode::Strict);
+
+        assert_eq!(calibration.unwrap().total, 281);
+    }
+
+    #[test]
+    fn overlapping_words_any_lexicon() {
+        let english = Matcher::new(&Lexicon::english());
+        let german = Lexicon::parse("eins 1\nzwei 2\nsieben 7\nacht 8").unwrap();
+        let french = Lexicon::parse("un 1\ndeux 2\nsix 6\nsept 7\nhuit 8").unwrap();
+
+        let german = Matcher::new(&german);
+        let french = Matcher::new(&french);
+
+        assert_eq!(calibration_value("twone", &english), Ok(21));
+        assert_eq!(calibration_value("zweinsx", &german), Ok(21));
+        assert_eq!(calibration_value("xacht3sieben", &german), Ok(87));
+        assert_eq!(calibration_value("deuxsixsept", &french), Ok(27));
+    }
+
+    #[test]
+    fn multi_digit_words() {
+        let file = include_str!("../input/lexicon_en_extended.txt");
+        let lexicon = Matcher::new(&Lexicon::parse(file).unwrap());
+
+        assert_eq!(calibration_value("twelve", &lexicon), Ok(12));
+        assert_eq!(calibration_value("sixty", &lexicon), Ok(60));
+        assert_eq!(calibration_value("threeseventeen", &lexicon), Ok(37));
+        assert_eq!(calibration_value("twentyone", &lexicon), Ok(21));
+        assert_eq!(calibration_value("xseventeen", &lexicon), Ok(17));
+    }
+
+    #[test]
+    fn lenient_skips_lines_without_words() {
+        let file = vec!["two1nine", "xyz", "", "eightwothree"];
+        let lexicon = Lexicon::english();
+
+        let calibration = problem_two(file.clone(), &lexicon, Mode::Lenient).unwrap();
+        assert_eq!(
+            calibration,
+            Calibration {
+                total: 112,
+                skipped: 1
             }
         );
-        result_vec.sort_by(|(a, _, _), (x, _, _)| a.cmp(x) );
-        count += result_vec.first().unwrap().2 * 10 + result_vec.last().unwrap().2;
 
+        match problem_two(file, &lexicon, Mode::Strict) {
+            Err(CalibrationError::Line { line_number, .. }) => assert_eq!(line_number, 2),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn matcher_agrees_with_naive_scan() {
+        let file = include_str!("../input/prob_two_input.txt");
+        let lexicon = Lexicon::parse(include_str!("../input/lexicon_en_extended.txt")).unwrap();
+        let matcher = Matcher::new(&lexicon);
+
+        for line in file.lines().filter(|line| !line.is_empty()) {
+            assert_eq!(
+                calibration_value(line, &matcher),
+                Ok(crate::bench::naive_calibration_value(line, &lexicon)),
+                "{line}"
+            );
+        }
     }
-    count
 }
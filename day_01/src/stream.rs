@@ -0,0 +1,44 @@
+use std::io::{self, BufRead};
+
+/// Reads `reader` one line at a time, reusing a single buffer, and calls
+/// `visit` with the 1-based line number of every non-empty line. Trailing
+/// `\n`/`\r\n` is stripped first.
+pub fn for_each_line<R, F, E>(mut reader: R, mut visit: F) -> Result<(), E>
+where
+    R: BufRead,
+    F: FnMut(usize, &str) -> Result<(), E>,
+    E: From<io::Error>,
+{
+    let mut buffer = String::new();
+    let mut line_number: usize = 0;
+
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let line = buffer.trim_end_matches(['\n', '\r']);
+        if line.is_empty() {
+            continue;
+        }
+        visit(line_number, line)?;
+    }
+    Ok(())
+}
+
+/// Adds up `value` for every non-empty line of `reader`.
+pub fn sum_lines<R, F>(reader: R, mut value: F) -> io::Result<u64>
+where
+    R: BufRead,
+    F: FnMut(&str) -> u64,
+{
+    let mut count: u64 = 0;
+
+    for_each_line(reader, |_, line| -> io::Result<()> {
+        count += value(line);
+        Ok(())
+    })?;
+    Ok(count)
+}
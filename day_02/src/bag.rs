@@ -0,0 +1,178 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::Game;
+
+const INIT_GAME: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+#[derive(Debug)]
+pub enum BagError {
+    Io(io::Error),
+    BadEntry(String),
+    Duplicate(String),
+}
+
+impl fmt::Display for BagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagError::Io(err) => write!(f, "could not read bag: {err}"),
+            BagError::BadEntry(entry) => {
+                write!(f, "expected `<colour>=<count>` in bag, found {entry:?}")
+            }
+            BagError::Duplicate(colour) => write!(f, "colour {colour:?} is listed twice"),
+        }
+    }
+}
+
+impl std::error::Error for BagError {}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limits: Vec<String> = self
+            .limits
+            .iter()
+            .map(|(colour, count)| format!("{colour}={count}"))
+            .collect();
+        write!(f, "{}", limits.join(", "))
+    }
+}
+
+/// How the minimum bag power treats a colour the game never shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingColour {
+    /// The colour needs zero cubes, so the power is zero.
+    Zero,
+    /// The colour is left out of the product.
+    Skip,
+}
+
+/// How many cubes of each colour the bag holds. Colours keep the order they
+/// were given in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    limits: Vec<(String, u32)>,
+}
+
+impl Default for Bag {
+    fn default() -> Bag {
+        Bag::new(
+            INIT_GAME
+                .iter()
+                .map(|(colour, count)| (colour.to_string(), *count)),
+        )
+        .unwrap()
+    }
+}
+
+impl Bag {
+    pub fn new(limits: impl IntoIterator<Item = (String, u32)>) -> Result<Bag, BagError> {
+        let mut bag = Bag { limits: Vec::new() };
+
+        for (colour, count) in limits {
+            if bag.limit(&colour).is_some() {
+                return Err(BagError::Duplicate(colour));
+            }
+            bag.limits.push((colour, count));
+        }
+        Ok(bag)
+    }
+
+    /// Parses `<colour>=<count>` or `<colour> <count>` entries separated by
+    /// commas or newlines. Lines starting with `#` are ignored.
+    pub fn parse(spec: &str) -> Result<Bag, BagError> {
+        let limits = spec
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let bad_entry = || BagError::BadEntry(entry.to_string());
+                let (colour, count) = entry
+                    .split_once('=')
+                    .or_else(|| entry.split_once(char::is_whitespace))
+                    .ok_or_else(bad_entry)?;
+                let count = count.trim().parse::<u32>().map_err(|_| bad_entry())?;
+                Ok((colour.trim().to_string(), count))
+            })
+            .collect::<Result<Vec<_>, BagError>>()?;
+
+        Bag::new(limits)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Bag, BagError> {
+        Bag::parse(&fs::read_to_string(path).map_err(BagError::Io)?)
+    }
+
+    pub fn limit(&self, colour: &str) -> Option<u32> {
+        self.limits
+            .iter()
+            .find(|(name, _)| name == colour)
+            .map(|(_, count)| *count)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.limits.iter().map(|(colour, _)| colour.as_str())
+    }
+
+    /// Whether every round of `game` fits in the bag. Draws of the same
+    /// colour within a round add up, and a colour the bag does not hold can
+    /// never be drawn.
+    pub fn allows(&self, game: &Game) -> bool {
+        game.rounds.iter().all(|round| {
+            round
+                .draws
+                .iter()
+                .all(|draw| self.limit(&draw.colour).unwrap_or(0) >= round.count(&draw.colour))
+        })
+    }
+
+    /// The bag colours followed by any other colour `game` shows.
+    pub fn colours_with<'a>(&'a self, game: &'a Game) -> Vec<&'a str> {
+        let mut colours: Vec<&str> = self.colours().collect();
+
+        for draw in game.rounds.iter().flat_map(|round| &round.draws) {
+            if !colours.contains(&draw.colour.as_str()) {
+                colours.push(&draw.colour);
+            }
+        }
+        colours
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bag::*;
+
+    #[test]
+    fn parse_any_colours() {
+        let bag = Bag::parse("# custom\nred=2, purple 7\nteal=0").unwrap();
+
+        assert_eq!(bag.colours().collect::<Vec<_>>(), ["red", "purple", "teal"]);
+        assert_eq!(bag.limit("purple"), Some(7));
+        assert!(matches!(
+            Bag::parse("red=2,red=3"),
+            Err(BagError::Duplicate(_))
+        ));
+        assert!(matches!(Bag::parse("red"), Err(BagError::BadEntry(_))));
+    }
+
+    #[test]
+    fn unknown_colours_are_not_allowed() {
+        let bag = Bag::parse("red=12").unwrap();
+        let game: Game = "Game 1: 3 red; 1 blue".parse().unwrap();
+
+        assert!(!bag.allows(&game));
+        assert!(Bag::parse("red=12, blue=1").unwrap().allows(&game));
+    }
+
+    #[test]
+    fn repeated_colours_add_up() {
+        let game: Game = "Game 1: 7 red, 7 red".parse().unwrap();
+
+        assert!(!Bag::default().allows(&game));
+        assert!(Bag::parse("red=14").unwrap().allows(&game));
+    }
+}
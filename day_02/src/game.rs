@@ -0,0 +1,214 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub count: u32,
+    pub colour: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub draws: Vec<Draw>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    MissingHeader,
+    BadId(String),
+    EmptyRound { round: usize },
+    BadDraw { round: usize, draw: String },
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::MissingHeader => write!(f, "expected `Game <id>: ...`"),
+            GameError::BadId(id) => write!(f, "game id {id:?} is not a number"),
+            GameError::EmptyRound { round } => write!(f, "round {round} has no draws"),
+            GameError::BadDraw { round, draw } => {
+                write!(
+                    f,
+                    "round {round}: expected `<count> <colour>`, found {draw:?}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// A [`GameError`] together with the line of the game log it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line_number: usize,
+    pub line: String,
+    pub error: GameError,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {}: {:?}",
+            self.line_number, self.error, self.line
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Round {
+    /// Cubes of `colour` shown in this round, 0 when the colour is absent.
+    pub fn count(&self, colour: &str) -> u32 {
+        self.draws
+            .iter()
+            .filter(|draw| draw.colour == colour)
+            .map(|draw| draw.count)
+            .sum()
+    }
+}
+
+impl Game {
+    /// The most cubes of `colour` shown in any single round.
+    pub fn max_count(&self, colour: &str) -> u32 {
+        self.rounds
+            .iter()
+            .map(|round| round.count(colour))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn parse_round(round: usize, text: &str) -> Result<Round, GameError> {
+    if text.trim().is_empty() {
+        return Err(GameError::EmptyRound { round });
+    }
+
+    let draws = text
+        .split(',')
+        .map(|cell| {
+            let bad_draw = || GameError::BadDraw {
+                round,
+                draw: cell.trim().to_string(),
+            };
+            let mut split = cell.split_whitespace();
+            match (split.next(), split.next(), split.next()) {
+                (Some(count), Some(colour), None) => Ok(Draw {
+                    count: count.parse::<u32>().map_err(|_| bad_draw())?,
+                    colour: colour.to_string(),
+                }),
+                _ => Err(bad_draw()),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Round { draws })
+}
+
+impl FromStr for Game {
+    type Err = GameError;
+
+    fn from_str(line: &str) -> Result<Game, GameError> {
+        let (header, rounds) = line.split_once(':').ok_or(GameError::MissingHeader)?;
+        let id = header
+            .trim()
+            .strip_prefix("Game ")
+            .ok_or(GameError::MissingHeader)?
+            .trim();
+        let id = id
+            .parse::<u32>()
+            .map_err(|_| GameError::BadId(id.to_string()))?;
+
+        let rounds = rounds
+            .split(';')
+            .enumerate()
+            .map(|(index, round)| parse_round(index + 1, round))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Game { id, rounds })
+    }
+}
+
+/// Every colour shown in `games`, in order of first appearance.
+pub fn colours(games: &[Game]) -> Vec<&str> {
+    let mut colours: Vec<&str> = Vec::new();
+
+    for draw in games
+        .iter()
+        .flat_map(|game| &game.rounds)
+        .flat_map(|round| &round.draws)
+    {
+        if !colours.contains(&draw.colour.as_str()) {
+            colours.push(&draw.colour);
+        }
+    }
+    colours
+}
+
+/// Parses one game per non-empty line.
+pub fn parse_games(file: &str) -> Result<Vec<Game>, ParseError> {
+    file.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.parse::<Game>().map_err(|error| ParseError {
+                line_number: index + 1,
+                line: line.to_string(),
+                error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::*;
+
+    #[test]
+    fn keeps_round_boundaries() {
+        let game: Game = "Game 12: 3 blue, 4 red; 1 red; 2 green, 6 blue"
+            .parse()
+            .unwrap();
+
+        assert_eq!(game.id, 12);
+        assert_eq!(game.rounds.len(), 3);
+        assert_eq!(
+            game.rounds[1].draws,
+            [Draw {
+                count: 1,
+                colour: "red".to_string()
+            }]
+        );
+        assert_eq!(game.max_count("blue"), 6);
+        assert_eq!(game.max_count("purple"), 0);
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let bad = |line: &str| line.parse::<Game>().unwrap_err();
+
+        assert_eq!(bad("3 blue, 4 red"), GameError::MissingHeader);
+        assert_eq!(bad("Game x: 3 blue"), GameError::BadId("x".to_string()));
+        assert_eq!(
+            bad("Game 1: 3 blue;; 1 red"),
+            GameError::EmptyRound { round: 2 }
+        );
+        assert_eq!(
+            bad("Game 1: 3 blue; 1 red, three green"),
+            GameError::BadDraw {
+                round: 2,
+                draw: "three green".to_string()
+            }
+        );
+
+        let error = parse_games("Game 1: 1 red\n\nGame 2: red\n").unwrap_err();
+        assert_eq!(error.line_number, 3);
+    }
+}
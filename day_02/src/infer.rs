@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::bag::Bag;
+use crate::game::{self, Game};
+
+/// Every bag holding at least `at_least` cubes of `colour` is consistent
+/// with the games; there is no upper bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feasible {
+    pub colour: String,
+    pub at_least: u32,
+    /// Ids of the games whose rounds reach `at_least`.
+    pub set_by: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGame(pub u32);
+
+impl fmt::Display for UnknownGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no game with id {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownGame {}
+
+/// The per-colour lower bounds a bag must meet for every game to be possible.
+pub fn feasible_region(games: &[Game]) -> Vec<Feasible> {
+    game::colours(games)
+        .into_iter()
+        .map(|colour| {
+            let at_least = games
+                .iter()
+                .map(|game| game.max_count(colour))
+                .max()
+                .unwrap_or(0);
+            let set_by = games
+                .iter()
+                .filter(|game| game.max_count(colour) == at_least)
+                .map(|game| game.id)
+                .collect();
+
+            Feasible {
+                colour: colour.to_string(),
+                at_least,
+                set_by,
+            }
+        })
+        .collect()
+}
+
+/// The smallest bag under which every game in `games` is possible.
+pub fn smallest_bag(games: &[Game]) -> Bag {
+    let limits = feasible_region(games)
+        .into_iter()
+        .map(|feasible| (feasible.colour, feasible.at_least));
+
+    Bag::new(limits).unwrap()
+}
+
+/// The smallest bag under which every game listed in `ids` is possible.
+pub fn smallest_bag_for(games: &[Game], ids: &[u32]) -> Result<Bag, UnknownGame> {
+    let subset = ids
+        .iter()
+        .map(|id| {
+            games
+                .iter()
+                .find(|game| game.id == *id)
+                .cloned()
+                .ok_or(UnknownGame(*id))
+        })
+        .collect::<Result<Vec<Game>, _>>()?;
+
+    Ok(smallest_bag(&subset))
+}
+
+/// For each colour, the smallest count that covers more than half of the
+/// games on its own.
+pub fn majority_bag(games: &[Game]) -> Bag {
+    let limits = game::colours(games).into_iter().map(|colour| {
+        let mut maxima: Vec<u32> = games.iter().map(|game| game.max_count(colour)).collect();
+        maxima.sort_unstable();
+        (colour.to_string(), maxima[maxima.len() / 2])
+    });
+
+    Bag::new(limits).unwrap()
+}
+
+/// Ids of the games that are impossible under [`majority_bag`].
+pub fn conflicting_games(games: &[Game]) -> Vec<u32> {
+    let majority = majority_bag(games);
+
+    games
+        .iter()
+        .filter(|game| !majority.allows(game))
+        .map(|game| game.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infer::*;
+
+    fn sample() -> Vec<Game> {
+        game::parse_games(include_str!("../input/input_sample_one.txt")).unwrap()
+    }
+
+    #[test]
+    fn feasible_region_of_sample() {
+        let region = feasible_region(&sample());
+        let bounds: Vec<(&str, u32, &[u32])> = region
+            .iter()
+            .map(|f| (f.colour.as_str(), f.at_least, f.set_by.as_slice()))
+            .collect();
+
+        assert_eq!(
+            bounds,
+            [
+                ("blue", 15, &[4][..]),
+                ("red", 20, &[3][..]),
+                ("green", 13, &[3][..])
+            ]
+        );
+    }
+
+    #[test]
+    fn majority_and_subsets() {
+        let games = sample();
+
+        assert_eq!(
+            majority_bag(&games),
+            Bag::parse("blue=6, red=6, green=3").unwrap()
+        );
+        assert_eq!(conflicting_games(&games), [3, 4]);
+        assert_eq!(
+            smallest_bag_for(&games, &[1, 5]).unwrap(),
+            Bag::parse("blue=6, red=6, green=3").unwrap()
+        );
+        assert_eq!(smallest_bag_for(&games, &[9]), Err(UnknownGame(9)));
+    }
+}
@@ -1,18 +1,217 @@
+mod bag;
+mod game;
+mod infer;
 mod prob_one;
 mod prob_two;
+mod query;
+mod stats;
+
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+
+use bag::{Bag, MissingColour};
+use game::Game;
+use query::{Context, Query};
+
+const USAGE: &str =
+    "usage: day_02 [--bag red=12,green=13,blue=14 | --bag-file FILE] [--skip-missing] [PATH]
+       day_02 [--bag ... | --bag-file FILE] --query EXPR [PATH]
+       day_02 --infer [--subset ID,ID,...] [PATH]
+       day_02 --stats games|colours [PATH]";
+
+struct Args {
+    path: Option<String>,
+    bag: Option<String>,
+    bag_file: Option<String>,
+    missing: MissingColour,
+    infer: bool,
+    subset: Option<Vec<u32>>,
+    query: Option<String>,
+    stats: Option<String>,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut args = Args {
+        path: None,
+        bag: None,
+        bag_file: None,
+        missing: MissingColour::Zero,
+        infer: false,
+        subset: None,
+        query: None,
+        stats: None,
+    };
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--bag" => args.bag = Some(iter.next()?),
+            "--bag-file" => args.bag_file = Some(iter.next()?),
+            "--skip-missing" => args.missing = MissingColour::Skip,
+            "--infer" => args.infer = true,
+            "--query" => args.query = Some(iter.next()?),
+            "--stats" => match iter.next()?.as_str() {
+                per @ ("games" | "colours") => args.stats = Some(per.to_string()),
+                _ => return None,
+            },
+            "--subset" => {
+                let ids = iter.next()?;
+                let ids = ids.split(',').map(|id| id.trim().parse::<u32>().ok());
+                args.subset = Some(ids.collect::<Option<Vec<_>>>()?);
+            }
+            _ if args.path.is_none() && !arg.starts_with("--") => args.path = Some(arg),
+            _ => return None,
+        }
+    }
+    Some(args)
+}
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("day_02: {err}");
+    process::exit(1);
+}
+
+fn load_games(file: &str) -> Vec<Game> {
+    game::parse_games(file).unwrap_or_else(|err| fail(err))
+}
+
+fn run_infer(games: &[Game], subset: Option<&[u32]>) {
+    println!("{:<8} {:>8}  set by games", "colour", "at least");
+    for feasible in infer::feasible_region(games) {
+        let ids: Vec<String> = feasible.set_by.iter().map(u32::to_string).collect();
+        println!(
+            "{:<8} {:>8}  {}",
+            feasible.colour,
+            feasible.at_least,
+            ids.join(", ")
+        );
+    }
+
+    let conflicting: Vec<String> = infer::conflicting_games(games)
+        .iter()
+        .map(u32::to_string)
+        .collect();
+    println!("majority bag: {}", infer::majority_bag(games));
+    println!("conflicting games: {}", conflicting.join(", "));
+
+    if let Some(ids) = subset {
+        let bag = infer::smallest_bag_for(games, ids).unwrap_or_else(|err| fail(err));
+        let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+        println!("smallest bag for {}: {}", ids.join(", "), bag);
+    }
+}
+
+fn run_query(games: &[Game], query: &Query, context: &Context) {
+    let ids: Vec<u32> = games
+        .iter()
+        .filter(|game| query.matches(game, context))
+        .map(|game| game.id)
+        .collect();
+    let listed: Vec<String> = ids.iter().map(u32::to_string).collect();
+
+    println!("matching games: {}", listed.join(", "));
+    println!("sum: {}", ids.iter().sum::<u32>());
+}
 
 fn main() {
-    let file = include_str!("../input/prob_one_input.txt");
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    };
+
+    let bag = match (&args.bag, &args.bag_file) {
+        (Some(spec), None) => Bag::parse(spec),
+        (None, Some(path)) => Bag::from_file(path),
+        (None, None) => Ok(Bag::default()),
+        (Some(_), Some(_)) => fail("--bag and --bag-file are exclusive"),
+    }
+    .unwrap_or_else(|err| fail(err));
+
+    let (file, file_2) = match &args.path {
+        Some(path) => {
+            let file = fs::read_to_string(path).unwrap_or_else(|err| fail(err));
+            (file.clone(), file)
+        }
+        None => (
+            include_str!("../input/prob_one_input.txt").to_string(),
+            include_str!("../input/prob_two_input.txt").to_string(),
+        ),
+    };
+
+    let context = Context {
+        bag: &bag,
+        missing: args.missing,
+    };
+
+    let games = load_games(&file);
+    if args.infer {
+        return run_infer(&games, args.subset.as_deref());
+    }
+    if let Some(per) = &args.stats {
+        let mut out = io::stdout().lock();
+        let written = match per.as_str() {
+            "games" => stats::write_game_csv(&mut out, &games),
+            _ => stats::write_colour_csv(&mut out, &games),
+        };
+        return written
+            .and_then(|_| out.flush())
+            .unwrap_or_else(|err| fail(err));
+    }
+    if let Some(query) = &args.query {
+        let query: Query = query.parse().unwrap_or_else(|err| fail(err));
+        return run_query(&games, &query, &context);
+    }
+
+    let possible = Query::possible(&bag);
     println!(
         "{:}",
-        file.lines().map(|x| prob_one::problem_one(x)).sum::<u32>()
+        games
+            .iter()
+            .map(|game| prob_one::problem_one(game, &possible, &context))
+            .sum::<u32>()
     );
 
     println!("===================");
 
-    let file = include_str!("../input/prob_two_input.txt");
+    let games = load_games(&file_2);
     println!(
         "{:}",
-        file.lines().map(|x| prob_two::problem_two(x)).sum::<u64>()
+        games
+            .iter()
+            .map(|game| prob_two::problem_two(game, &bag, args.missing))
+            .sum::<u64>()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn sample_totals() {
+        let games = game::parse_games(include_str!("../input/input_sample_one.txt")).unwrap();
+        let bag = Bag::default();
+        let context = Context {
+            bag: &bag,
+            missing: MissingColour::Zero,
+        };
+        let possible = Query::possible(&bag);
+
+        assert_eq!(
+            games
+                .iter()
+                .map(|game| prob_one::problem_one(game, &possible, &context))
+                .sum::<u32>(),
+            8
+        );
+        assert_eq!(
+            games
+                .iter()
+                .map(|game| prob_two::problem_two(game, &bag, MissingColour::Zero))
+                .sum::<u64>(),
+            2286
+        );
+    }
+}
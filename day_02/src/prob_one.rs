@@ -1,29 +1,12 @@
-const INIT_GAME: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+use crate::game::Game;
+use crate::query::{Context, Query};
 
-pub fn problem_one(line: &str) -> u32 {
-    let mut bag = line
-        .split(|c: char| c == ':' || c == ';' || c == ',')
-        .map(|cell| {
-            let mut split = cell.trim().split(|b: char| b == ' ');
-            (split.next().unwrap(), split.next().unwrap())
-        })
-        .collect::<Vec<_>>();
-    let val = bag.first().unwrap().1.parse::<u32>().unwrap();
-    bag.remove(0);
-    let bag_uncovered = bag
-        .iter()
-        .map(|tuple| {
-            let temp = INIT_GAME
-                .iter()
-                .find(|cell| cell.0 == tuple.1 && cell.1 >= (tuple.0.parse::<u32>().unwrap()));
-            temp
-        })
-        .filter(|x| x.is_none())
-        .collect::<Vec<_>>();
-
-    if bag_uncovered.len() != 0 {
-        return 0;
+/// The id of `game` when it matches `query`, 0 otherwise. Part one uses
+/// [`Query::possible`].
+pub fn problem_one(game: &Game, query: &Query, context: &Context) -> u32 {
+    if query.matches(game, context) {
+        game.id
     } else {
-        return val;
+        0
     }
 }
@@ -1,27 +1,26 @@
-const INIT_GAME: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+use crate::bag::{Bag, MissingColour};
+use crate::game::Game;
 
-pub fn problem_two(line: &str) -> u64 {
-    let bag: Vec<(&str, &str)> = line
-        .split(|c: char| c == ':' || c == ';' || c == ',')
-        .map(|cell| {
-            let mut split = cell.trim().split(|b: char| b == ' ');
-            (split.next().unwrap(), split.next().unwrap())
-        })
-        .skip(1)
-        .collect::<Vec<_>>();
-
-    let (mut red_bag, remaning_color): (Vec<(u64, &str)>, Vec<(u64, &str)>) = bag
+/// Product of the fewest cubes of each colour that make `game` possible,
+/// over the bag colours and any extra colour the game shows.
+pub fn problem_two(game: &Game, bag: &Bag, missing: MissingColour) -> u64 {
+    bag.colours_with(game)
         .into_iter()
-        .map(|(val, color)| (val.parse::<u64>().unwrap(), color))
-        .partition(|(val, color)| *color == "red");
+        .map(|colour| game.max_count(colour) as u64)
+        .filter(|count| *count > 0 || missing == MissingColour::Zero)
+        .product()
+}
 
-    let (mut green_bag, mut blue_bag): (Vec<(u64, &str)>, Vec<(u64, &str)>) = remaning_color
-        .into_iter()
-        .partition(|(val, color)| *color == "green");
+#[cfg(test)]
+mod tests {
+    use crate::prob_two::*;
 
-    red_bag.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    green_bag.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    blue_bag.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    #[test]
+    fn missing_colours() {
+        let game: Game = "Game 1: 3 red, 2 purple; 4 red".parse().unwrap();
+        let bag = Bag::default();
 
-    red_bag.last().unwrap().0 * green_bag.last().unwrap().0 * blue_bag.last().unwrap().0
+        assert_eq!(problem_two(&game, &bag, MissingColour::Zero), 0);
+        assert_eq!(problem_two(&game, &bag, MissingColour::Skip), 8);
+    }
 }
@@ -0,0 +1,450 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bag::{Bag, MissingColour};
+use crate::game::{Game, Round};
+use crate::prob_two;
+
+/// What a query needs besides the game: the bag `power` is measured against.
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    pub bag: &'a Bag,
+    pub missing: MissingColour,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// A number in a comparison. A colour means the cubes shown in the current
+/// round inside `any`/`all`, and the most shown in any round outside.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Number(u64),
+    Id,
+    Power,
+    Rounds,
+    Colour(String),
+}
+
+/// A predicate over one game.
+///
+/// ```text
+/// expr    := and ("or" and)*
+/// and     := unary ("and" unary)*
+/// unary   := "not" unary | primary
+/// primary := "(" expr ")"
+///          | ("any" | "all") "(" expr ")"
+///          | "only" "(" colour ("," colour)* ")"
+///          | value ("<" | "<=" | ">" | ">=" | "==" | "!=") value
+/// value   := number | "id" | "power" | "rounds" | colour
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Or(Box<Query>, Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    /// True when the inner query holds for at least one round.
+    Any(Box<Query>),
+    /// True when the inner query holds for every round.
+    All(Box<Query>),
+    /// True when the game shows no colour outside the list.
+    Only(Vec<String>),
+    Compare(Value, Cmp, Value),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "query column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Number(u64),
+    Cmp(Cmp),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let column = index + 1;
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            ',' => Token::Comma,
+            '<' | '>' | '=' | '!' => {
+                let equals = chars.next_if(|(_, next)| *next == '=').is_some();
+                Token::Cmp(match (c, equals) {
+                    ('<', false) => Cmp::Lt,
+                    ('<', true) => Cmp::Le,
+                    ('>', false) => Cmp::Gt,
+                    ('>', true) => Cmp::Ge,
+                    ('=', true) => Cmp::Eq,
+                    ('!', true) => Cmp::Ne,
+                    _ => {
+                        return Err(QueryError {
+                            column,
+                            message: format!("expected `{c}=`"),
+                        })
+                    }
+                })
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = index + 1;
+                while let Some((next, _)) = chars.next_if(|(_, next)| next.is_ascii_digit()) {
+                    end = next + 1;
+                }
+                let number = text[index..end].parse::<u64>().map_err(|_| QueryError {
+                    column,
+                    message: format!("number {} is too large", &text[index..end]),
+                })?;
+                Token::Number(number)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = index + c.len_utf8();
+                while let Some((next, next_c)) =
+                    chars.next_if(|(_, next)| next.is_alphanumeric() || matches!(next, '_' | '-'))
+                {
+                    end = next + next_c.len_utf8();
+                }
+                Token::Word(text[index..end].to_string())
+            }
+            c => {
+                return Err(QueryError {
+                    column,
+                    message: format!("unexpected character {c:?}"),
+                })
+            }
+        };
+        tokens.push((column, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    end_column: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end_column, |(column, _)| *column)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, QueryError> {
+        Err(QueryError {
+            column: self.column(),
+            message: message.into(),
+        })
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Word(next)) if next == word);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: Token, name: &str) -> Result<(), QueryError> {
+        if self.peek() == Some(&expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            self.error(format!("expected `{name}`"))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.and()?;
+        while self.eat_word("or") {
+            query = Query::Or(Box::new(query), Box::new(self.and()?));
+        }
+        Ok(query)
+    }
+
+    fn and(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.unary()?;
+        while self.eat_word("and") {
+            query = Query::And(Box::new(query), Box::new(self.unary()?));
+        }
+        Ok(query)
+    }
+
+    fn unary(&mut self) -> Result<Query, QueryError> {
+        if self.eat_word("not") {
+            return Ok(Query::Not(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Query, QueryError> {
+        if self.peek() == Some(&Token::Open) {
+            self.position += 1;
+            let query = self.expr()?;
+            self.expect(Token::Close, ")")?;
+            return Ok(query);
+        }
+
+        for (word, quantifier) in [("any", Query::Any as fn(_) -> _), ("all", Query::All)] {
+            if self.eat_word(word) {
+                self.expect(Token::Open, "(")?;
+                let query = self.expr()?;
+                self.expect(Token::Close, ")")?;
+                return Ok(quantifier(Box::new(query)));
+            }
+        }
+
+        if self.eat_word("only") {
+            self.expect(Token::Open, "(")?;
+            let mut colours = Vec::new();
+            loop {
+                match self.next() {
+                    Some(Token::Word(colour)) if !is_keyword(&colour) => colours.push(colour),
+                    _ => {
+                        self.position -= 1;
+                        return self.error("expected a colour");
+                    }
+                }
+                if self.peek() != Some(&Token::Comma) {
+                    break;
+                }
+                self.position += 1;
+            }
+            self.expect(Token::Close, ")")?;
+            return Ok(Query::Only(colours));
+        }
+
+        let lhs = self.value()?;
+        let cmp = match self.next() {
+            Some(Token::Cmp(cmp)) => cmp,
+            _ => {
+                self.position -= 1;
+                return self.error("expected a comparison");
+            }
+        };
+        let rhs = self.value()?;
+        Ok(Query::Compare(lhs, cmp, rhs))
+    }
+
+    fn value(&mut self) -> Result<Value, QueryError> {
+        let value = match self.peek() {
+            Some(Token::Number(number)) => Value::Number(*number),
+            Some(Token::Word(word)) => match word.as_str() {
+                "id" => Value::Id,
+                "power" => Value::Power,
+                "rounds" => Value::Rounds,
+                word if is_keyword(word) => return self.error(format!("unexpected `{word}`")),
+                colour => Value::Colour(colour.to_string()),
+            },
+            Some(_) => return self.error("expected a number, colour, id, power or rounds"),
+            None => return self.error("unexpected end of query"),
+        };
+        self.position += 1;
+        Ok(value)
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "and" | "or" | "not" | "any" | "all" | "only" | "id" | "power" | "rounds"
+    )
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(text: &str) -> Result<Query, QueryError> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+            end_column: text.len() + 1,
+        };
+
+        let query = parser.expr()?;
+        if parser.peek().is_some() {
+            return parser.error("unexpected trailing input");
+        }
+        Ok(query)
+    }
+}
+
+impl Cmp {
+    fn holds(self, lhs: u64, rhs: u64) -> bool {
+        match self {
+            Cmp::Lt => lhs < rhs,
+            Cmp::Le => lhs <= rhs,
+            Cmp::Gt => lhs > rhs,
+            Cmp::Ge => lhs >= rhs,
+            Cmp::Eq => lhs == rhs,
+            Cmp::Ne => lhs != rhs,
+        }
+    }
+}
+
+impl Value {
+    fn eval(&self, game: &Game, round: Option<&Round>, context: &Context) -> u64 {
+        match self {
+            Value::Number(number) => *number,
+            Value::Id => game.id as u64,
+            Value::Power => prob_two::problem_two(game, context.bag, context.missing),
+            Value::Rounds => game.rounds.len() as u64,
+            Value::Colour(colour) => match round {
+                Some(round) => round.count(colour) as u64,
+                None => game.max_count(colour) as u64,
+            },
+        }
+    }
+}
+
+impl Query {
+    /// The query part one answers: every round fits in `bag` and no colour
+    /// outside the bag is ever shown.
+    pub fn possible(bag: &Bag) -> Query {
+        let colours: Vec<String> = bag.colours().map(str::to_string).collect();
+        let fits = colours
+            .iter()
+            .map(|colour| {
+                let limit = bag.limit(colour).unwrap() as u64;
+                Query::Compare(Value::Colour(colour.clone()), Cmp::Le, Value::Number(limit))
+            })
+            .reduce(|lhs, rhs| Query::And(Box::new(lhs), Box::new(rhs)));
+
+        match fits {
+            Some(fits) => Query::And(
+                Box::new(Query::All(Box::new(fits))),
+                Box::new(Query::Only(colours)),
+            ),
+            None => Query::Only(colours),
+        }
+    }
+
+    pub fn matches(&self, game: &Game, context: &Context) -> bool {
+        self.eval(game, None, context)
+    }
+
+    fn eval(&self, game: &Game, round: Option<&Round>, context: &Context) -> bool {
+        match self {
+            Query::Or(lhs, rhs) => lhs.eval(game, round, context) || rhs.eval(game, round, context),
+            Query::And(lhs, rhs) => {
+                lhs.eval(game, round, context) && rhs.eval(game, round, context)
+            }
+            Query::Not(query) => !query.eval(game, round, context),
+            Query::Any(query) => game
+                .rounds
+                .iter()
+                .any(|round| query.eval(game, Some(round), context)),
+            Query::All(query) => game
+                .rounds
+                .iter()
+                .all(|round| query.eval(game, Some(round), context)),
+            Query::Only(colours) => game
+                .rounds
+                .iter()
+                .flat_map(|round| &round.draws)
+                .all(|draw| colours.contains(&draw.colour)),
+            Query::Compare(lhs, cmp, rhs) => cmp.holds(
+                lhs.eval(game, round, context),
+                rhs.eval(game, round, context),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game;
+    use crate::query::*;
+
+    fn matching(query: &str) -> Vec<u32> {
+        let games = game::parse_games(include_str!("../input/input_sample_one.txt")).unwrap();
+        let bag = Bag::default();
+        let context = Context {
+            bag: &bag,
+            missing: MissingColour::Zero,
+        };
+        let query: Query = query.parse().unwrap();
+
+        games
+            .iter()
+            .filter(|game| query.matches(game, &context))
+            .map(|game| game.id)
+            .collect()
+    }
+
+    #[test]
+    fn round_quantifiers() {
+        assert_eq!(matching("any(red > 10 and blue < 3)"), [] as [u32; 0]);
+        assert_eq!(matching("any(red > 10 and blue <= 6)"), [3]);
+        assert_eq!(matching("all(green >= 1)"), [2, 3, 4, 5]);
+        assert_eq!(matching("not any(red > 4) or id == 1"), [1, 2]);
+    }
+
+    #[test]
+    fn game_values() {
+        assert_eq!(matching("power > 1000"), [3]);
+        assert_eq!(matching("red >= 14 and rounds == 3"), [3, 4]);
+        assert_eq!(matching("only(red, blue)"), [] as [u32; 0]);
+    }
+
+    #[test]
+    fn possible_matches_bag() {
+        let games = game::parse_games(include_str!("../input/prob_one_input.txt")).unwrap();
+        let bag = Bag::parse("red=12, green=13, blue=14, purple=3").unwrap();
+        let context = Context {
+            bag: &bag,
+            missing: MissingColour::Zero,
+        };
+        let query = Query::possible(&bag);
+
+        for game in &games {
+            assert_eq!(query.matches(game, &context), bag.allows(game));
+        }
+    }
+
+    #[test]
+    fn parse_errors_carry_column() {
+        let error = |text: &str| text.parse::<Query>().unwrap_err().column;
+
+        assert_eq!(error("red > "), 7);
+        assert_eq!(error("any(red > 1"), 12);
+        assert_eq!(error("red = 1"), 5);
+        assert_eq!(error("red > 1 blue"), 9);
+    }
+}
@@ -0,0 +1,151 @@
+use std::io::{self, Write};
+
+use crate::game::{self, Game, Round};
+
+/// Cubes of one colour per round, over a set of rounds. A round that does
+/// not show the colour counts as 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColourStats {
+    pub colour: String,
+    pub rounds: usize,
+    /// Rounds that show the colour at all.
+    pub appearances: usize,
+    pub min: u32,
+    pub max: u32,
+    pub total: u64,
+}
+
+impl ColourStats {
+    pub fn new<'a>(colour: &str, rounds: impl Iterator<Item = &'a Round>) -> ColourStats {
+        let mut stats = ColourStats {
+            colour: colour.to_string(),
+            rounds: 0,
+            appearances: 0,
+            min: u32::MAX,
+            max: 0,
+            total: 0,
+        };
+
+        for round in rounds {
+            let count = round.count(colour);
+            stats.rounds += 1;
+            stats.min = stats.min.min(count);
+            stats.max = stats.max.max(count);
+            stats.total += count as u64;
+            if round.draws.iter().any(|draw| draw.colour == colour) {
+                stats.appearances += 1;
+            }
+        }
+        if stats.rounds == 0 {
+            stats.min = 0;
+        }
+        stats
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.total as f64 / self.rounds as f64
+        }
+    }
+}
+
+/// One row per game and colour of the log.
+pub fn game_stats(games: &[Game]) -> Vec<(u32, ColourStats)> {
+    let colours = game::colours(games);
+
+    games
+        .iter()
+        .flat_map(|game| {
+            colours
+                .iter()
+                .map(|colour| (game.id, ColourStats::new(colour, game.rounds.iter())))
+        })
+        .collect()
+}
+
+/// One row per colour over every round of the log, with the number of
+/// games that show the colour.
+pub fn colour_stats(games: &[Game]) -> Vec<(usize, ColourStats)> {
+    game::colours(games)
+        .into_iter()
+        .map(|colour| {
+            let shown_in = games
+                .iter()
+                .filter(|game| game.max_count(colour) > 0)
+                .count();
+            let rounds = games.iter().flat_map(|game| &game.rounds);
+            (shown_in, ColourStats::new(colour, rounds))
+        })
+        .collect()
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn write_game_csv<W: Write>(out: &mut W, games: &[Game]) -> io::Result<()> {
+    writeln!(out, "game,colour,rounds,appearances,min,max,mean")?;
+    for (id, stats) in game_stats(games) {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{:.3}",
+            id,
+            csv_field(&stats.colour),
+            stats.rounds,
+            stats.appearances,
+            stats.min,
+            stats.max,
+            stats.mean()
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_colour_csv<W: Write>(out: &mut W, games: &[Game]) -> io::Result<()> {
+    writeln!(out, "colour,games,rounds,appearances,min,max,mean")?;
+    for (shown_in, stats) in colour_stats(games) {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{:.3}",
+            csv_field(&stats.colour),
+            shown_in,
+            stats.rounds,
+            stats.appearances,
+            stats.min,
+            stats.max,
+            stats.mean()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+
+    #[test]
+    fn csv_for_sample() {
+        let games = game::parse_games(include_str!("../input/input_sample_one.txt")).unwrap();
+
+        let mut out = Vec::new();
+        write_game_csv(&mut out, &games[..1]).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "game,colour,rounds,appearances,min,max,mean\n\
+             1,blue,3,2,0,6,3.000\n\
+             1,red,3,2,0,4,1.667\n\
+             1,green,3,2,0,2,1.333\n"
+        );
+
+        let mut out = Vec::new();
+        write_colour_csv(&mut out, &games).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().nth(2), Some("red,5,14,11,0,20,4.357"));
+    }
+}
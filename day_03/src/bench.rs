@@ -0,0 +1,122 @@
+use std::time::Instant;
+
+use crate::index::Neighbourhood;
+use crate::schematic::{Number, Schematic, Symbol};
+use crate::{prob_one, prob_two};
+
+/// Above this size the quadratic scan is skipped; it would run for hours.
+const NAIVE_LIMIT: usize = 600;
+
+fn touches(number: &Number, symbol: &Symbol) -> bool {
+    number.points().any(|point| {
+        point.row.abs_diff(symbol.symbol_point.row) <= 1
+            && point.column.abs_diff(symbol.symbol_point.column) <= 1
+    })
+}
+
+/// Part one before the index: every number against every symbol.
+pub fn naive_problem_one(schematic: &Schematic) -> u64 {
+    schematic
+        .numbers
+        .iter()
+        .filter(|number| {
+            schematic
+                .symbols
+                .iter()
+                .any(|symbol| touches(number, symbol))
+        })
+        .map(|number| number.value)
+        .fold(0, u64::saturating_add)
+}
+
+/// Part two before the index: every gear against every number.
+pub fn naive_problem_two(schematic: &Schematic) -> u64 {
+    schematic
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.character == '*')
+        .map(|symbol| {
+            schematic
+                .numbers
+                .iter()
+                .filter(|number| touches(number, symbol))
+                .collect::<Vec<_>>()
+        })
+        .filter(|adjacent| adjacent.len() == 2)
+        .map(|adjacent| {
+            adjacent
+                .iter()
+                .map(|number| number.value)
+                .fold(1, u64::saturating_mul)
+        })
+        .fold(0, u64::saturating_add)
+}
+
+/// xorshift64, so the synthetic schematic is the same on every run.
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// A `size` x `size` schematic, roughly a third digits and one cell in
+/// twenty a symbol.
+pub fn synthetic(size: usize) -> String {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut text = String::with_capacity(size * (size + 1));
+
+    for _ in 0..size {
+        for _ in 0..size {
+            let roll = next_random(&mut state) % 60;
+            text.push(match roll {
+                0..=19 => char::from(b'0' + (roll % 10) as u8),
+                20 => '*',
+                21 => '#',
+                22 => '+',
+                _ => '.',
+            });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn time<T: std::fmt::Display>(name: &str, run: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = run();
+    println!("{name:>13}: {result} in {:?}", start.elapsed());
+    result
+}
+
+pub fn run(size: usize) -> Result<(), String> {
+    let text = synthetic(size);
+    let start = Instant::now();
+    let schematic = Schematic::parse(&text).map_err(|err| err.to_string())?;
+    println!(
+        "{:>13}: {} numbers in {:?}",
+        "parse",
+        schematic.numbers.len(),
+        start.elapsed()
+    );
+    drop(text);
+
+    let one = time("indexed one", || {
+        prob_one::problem_one(&schematic, Neighbourhood::Eight)
+    });
+    let two = time("indexed two", || {
+        prob_two::problem_two(&schematic, Neighbourhood::Eight)
+    });
+
+    if size > NAIVE_LIMIT {
+        println!("naive scan skipped above {NAIVE_LIMIT} x {NAIVE_LIMIT}");
+        return Ok(());
+    }
+    let naive_one = time("naive one", || naive_problem_one(&schematic));
+    let naive_two = time("naive two", || naive_problem_two(&schematic));
+
+    if (one, two) != (naive_one, naive_two) {
+        return Err("naive and indexed totals differ".to_string());
+    }
+    Ok(())
+}
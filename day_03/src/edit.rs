@@ -0,0 +1,350 @@
+use std::fmt;
+
+use crate::index::{self, Neighbourhood};
+use crate::query::{SymbolMatch, SymbolQuery};
+use crate::schematic::{self, Number, Point, Schematic, Symbol};
+
+const EMPTY: u32 = u32::MAX;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    OutOfBounds(Point),
+    Newline,
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EditError::OutOfBounds(point) => write!(
+                f,
+                "cell {},{} is outside the schematic",
+                point.row, point.column
+            ),
+            EditError::Newline => write!(f, "a cell cannot hold a line break"),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+/// A schematic that can be edited one cell at a time while keeping the part
+/// number sum and the gear sum current.
+///
+/// An edit only revisits the digit run it touches on its row and the cells
+/// within reach of that run, so its cost does not depend on the size of the
+/// grid. The sums are kept exactly, so removing a contribution always undoes
+/// adding it, and saturate at `u64::MAX` when read, as a full recompute does.
+#[derive(Debug, Clone)]
+pub struct EditableSchematic {
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+    /// Numbers by id. Ids of removed numbers are reused through `free`.
+    numbers: Vec<Option<Number>>,
+    free: Vec<u32>,
+    number_at: Vec<u32>,
+    neighbourhood: Neighbourhood,
+    gears: SymbolQuery,
+    part_sum: u128,
+    gear_sum: u128,
+}
+
+impl EditableSchematic {
+    pub fn new(
+        schematic: &Schematic,
+        neighbourhood: Neighbourhood,
+        gears: SymbolQuery,
+    ) -> EditableSchematic {
+        let mut editable = EditableSchematic {
+            width: schematic.width,
+            height: schematic.height(),
+            cells: schematic.cells.clone(),
+            numbers: Vec::new(),
+            free: Vec::new(),
+            number_at: vec![EMPTY; schematic.cells.len()],
+            neighbourhood,
+            gears,
+            part_sum: 0,
+            gear_sum: 0,
+        };
+
+        for number in &schematic.numbers {
+            let id = editable.insert(number.clone());
+            editable.part_sum += u128::from(editable.part_value(id));
+        }
+        for symbol in &schematic.symbols {
+            let value = editable.gear_value(symbol.symbol_point);
+            editable.gear_sum += u128::from(value);
+        }
+        editable
+    }
+
+    /// Part one: the sum of every number touching a symbol.
+    pub fn part_sum(&self) -> u64 {
+        u64::try_from(self.part_sum).unwrap_or(u64::MAX)
+    }
+
+    /// Part two, or whatever `gears` selects: the sum of the aggregate over
+    /// every selected symbol.
+    pub fn gear_sum(&self) -> u64 {
+        u64::try_from(self.gear_sum).unwrap_or(u64::MAX)
+    }
+
+    /// The current grid as a freshly parsed schematic.
+    pub fn to_schematic(&self) -> Schematic {
+        let text: String = self
+            .cells
+            .chunks(self.width)
+            .flat_map(|line| line.iter().copied().chain(Some('\n')))
+            .collect();
+        Schematic::parse(&text).expect("grid keeps its shape")
+    }
+
+    pub fn clear(&mut self, point: Point) -> Result<(), EditError> {
+        self.set(point, '.')
+    }
+
+    /// Writes `character` into the cell at `point`. Writing or removing a
+    /// digit may extend, merge, split or drop the numbers on either side.
+    pub fn set(&mut self, point: Point, character: char) -> Result<(), EditError> {
+        let pos = self.position(point)?;
+        if character == '\n' || character == '\r' {
+            return Err(EditError::Newline);
+        }
+        if self.cells[pos] == character {
+            return Ok(());
+        }
+
+        // The runs that include or border the cell are rebuilt from scratch;
+        // together they span `lo..=hi`, which is bounded by non-digits.
+        let row = point.row;
+        let mut removed: Vec<u32> = Vec::new();
+        let (mut lo, mut hi) = (point.column, point.column);
+        for column in point.column.saturating_sub(1)..(point.column + 2).min(self.width) {
+            let id = self.number_at[row * self.width + column];
+            if id != EMPTY && !removed.contains(&id) {
+                let number = self.number(id);
+                lo = lo.min(number.start.column);
+                hi = hi.max(number.start.column + number.len - 1);
+                removed.push(id);
+            }
+        }
+
+        // Numbers whose part status may change: the rebuilt ones and any
+        // within reach of the edited cell, which may gain or lose a symbol.
+        // Symbols whose value may change: any within reach of the span.
+        let symbols: Vec<Point> = self.around(row, lo, hi - lo + 1).collect();
+        let mut stale = removed.clone();
+        for id in self.adjacent_numbers(point) {
+            if !stale.contains(&id) {
+                stale.push(id);
+            }
+        }
+
+        for &id in &stale {
+            self.part_sum -= u128::from(self.part_value(id));
+        }
+        for &near in &symbols {
+            self.gear_sum -= u128::from(self.gear_value(near));
+        }
+
+        self.cells[pos] = character;
+        for id in removed {
+            self.remove(id);
+        }
+        let line = &self.cells[row * self.width..(row + 1) * self.width];
+        let rebuilt: Vec<u32> = schematic::scan_numbers(line, row, lo..hi + 1)
+            .into_iter()
+            .map(|number| self.insert(number))
+            .collect();
+
+        let mut fresh = rebuilt;
+        for id in self.adjacent_numbers(point) {
+            if !fresh.contains(&id) {
+                fresh.push(id);
+            }
+        }
+        for &id in &fresh {
+            self.part_sum += u128::from(self.part_value(id));
+        }
+        for &near in &symbols {
+            self.gear_sum += u128::from(self.gear_value(near));
+        }
+        Ok(())
+    }
+
+    fn position(&self, point: Point) -> Result<usize, EditError> {
+        if point.row >= self.height || point.column >= self.width {
+            return Err(EditError::OutOfBounds(point));
+        }
+        Ok(point.row * self.width + point.column)
+    }
+
+    fn around(&self, row: usize, start: usize, len: usize) -> impl Iterator<Item = Point> {
+        index::around(self.neighbourhood, self.width, self.height, row, start, len)
+    }
+
+    fn cell(&self, point: Point) -> char {
+        self.cells[point.row * self.width + point.column]
+    }
+
+    fn number(&self, id: u32) -> &Number {
+        self.numbers[id as usize].as_ref().expect("live number id")
+    }
+
+    fn insert(&mut self, number: Number) -> u32 {
+        let id = match self.free.pop() {
+            Some(id) => id,
+            None => {
+                self.numbers.push(None);
+                (self.numbers.len() - 1) as u32
+            }
+        };
+        for point in number.points() {
+            self.number_at[point.row * self.width + point.column] = id;
+        }
+        self.numbers[id as usize] = Some(number);
+        id
+    }
+
+    fn remove(&mut self, id: u32) {
+        let number = self.numbers[id as usize].take().expect("live number id");
+        for point in number.points() {
+            self.number_at[point.row * self.width + point.column] = EMPTY;
+        }
+        self.free.push(id);
+    }
+
+    /// Ids of the numbers touching `point`, in reading order of first touch.
+    fn adjacent_numbers(&self, point: Point) -> Vec<u32> {
+        let mut ids: Vec<u32> = Vec::new();
+
+        for near in self.around(point.row, point.column, 1) {
+            let id = self.number_at[near.row * self.width + near.column];
+            if id != EMPTY && !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    /// What number `id` adds to the part sum: its value if a symbol touches
+    /// it, otherwise 0.
+    fn part_value(&self, id: u32) -> u64 {
+        let number = self.number(id);
+        let touched = self
+            .around(number.start.row, number.start.column, number.len)
+            .any(|near| schematic::is_symbol(self.cell(near)));
+        if touched {
+            number.value
+        } else {
+            0
+        }
+    }
+
+    /// What the cell at `point` adds to the gear sum: its aggregate if it is
+    /// a symbol selected by `gears`, otherwise 0.
+    fn gear_value(&self, point: Point) -> u64 {
+        let character = self.cell(point);
+        if !schematic::is_symbol(character) {
+            return 0;
+        }
+
+        let symbol = Symbol {
+            symbol_point: point,
+            character,
+        };
+        let found = SymbolMatch {
+            symbol: &symbol,
+            numbers: self
+                .adjacent_numbers(point)
+                .into_iter()
+                .map(|id| self.number(id))
+                .collect(),
+        };
+        if self.gears.matches(&found) {
+            found.value(self.gears.aggregate)
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::edit::*;
+    use crate::testing::next_random;
+    use crate::{prob_one, prob_two};
+
+    fn recomputed(editable: &EditableSchematic, neighbourhood: Neighbourhood) -> (u64, u64) {
+        let schematic = editable.to_schematic();
+        (
+            prob_one::problem_one(&schematic, neighbourhood),
+            prob_two::problem_two(&schematic, neighbourhood),
+        )
+    }
+
+    #[test]
+    fn merges_and_splits_numbers() {
+        let schematic = Schematic::parse("12.34\n..*..\n").unwrap();
+        let mut editable =
+            EditableSchematic::new(&schematic, Neighbourhood::Eight, SymbolQuery::gears());
+        assert_eq!((editable.part_sum(), editable.gear_sum()), (46, 12 * 34));
+
+        let middle = Point { row: 0, column: 2 };
+        editable.set(middle, '5').unwrap();
+        assert_eq!((editable.part_sum(), editable.gear_sum()), (12534, 0));
+
+        editable.clear(Point { row: 0, column: 1 }).unwrap();
+        assert_eq!((editable.part_sum(), editable.gear_sum()), (534, 0));
+
+        editable.set(middle, '*').unwrap();
+        assert_eq!((editable.part_sum(), editable.gear_sum()), (34, 0));
+    }
+
+    #[test]
+    fn sums_saturate_and_recover() {
+        let schematic = Schematic::parse("9999999999*999999999.\n").unwrap();
+        let mut editable =
+            EditableSchematic::new(&schematic, Neighbourhood::Eight, SymbolQuery::gears());
+        assert_eq!(editable.gear_sum(), 9999999999 * 999999999);
+
+        let end = Point { row: 0, column: 20 };
+        editable.set(end, '9').unwrap();
+        assert_eq!(editable.gear_sum(), u64::MAX);
+
+        editable.clear(end).unwrap();
+        assert_eq!(editable.gear_sum(), 9999999999 * 999999999);
+    }
+
+    #[test]
+    fn random_edits_match_full_recompute() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = || next_random(&mut state);
+        let palette = ['.', '.', '.', '*', '#', '1', '2', '5', '7', '9'];
+
+        for neighbourhood in [
+            Neighbourhood::Four,
+            Neighbourhood::Eight,
+            Neighbourhood::Radius(2),
+        ] {
+            let schematic =
+                Schematic::parse(include_str!("../input/input_sample_one.txt")).unwrap();
+            let mut editable =
+                EditableSchematic::new(&schematic, neighbourhood, SymbolQuery::gears());
+
+            for _ in 0..2_000 {
+                let point = Point {
+                    row: next() as usize % schematic.height(),
+                    column: next() as usize % schematic.width,
+                };
+                let character = palette[next() as usize % palette.len()];
+                editable.set(point, character).unwrap();
+                assert_eq!(
+                    (editable.part_sum(), editable.gear_sum()),
+                    recomputed(&editable, neighbourhood)
+                );
+            }
+        }
+    }
+}
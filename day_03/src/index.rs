@@ -0,0 +1,175 @@
+use crate::schematic::{self, Number, Point, Schematic};
+
+const EMPTY: u32 = u32::MAX;
+
+/// Which cells count as touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Neighbourhood {
+    /// Sharing an edge.
+    Four,
+    /// Sharing an edge or a corner.
+    #[default]
+    Eight,
+    /// Within this many rows and columns, so `Radius(1)` is `Eight`.
+    Radius(usize),
+}
+
+impl Neighbourhood {
+    fn reach(self) -> usize {
+        match self {
+            Neighbourhood::Four | Neighbourhood::Eight => 1,
+            Neighbourhood::Radius(radius) => radius,
+        }
+    }
+
+    /// Whether a cell `rows` and `columns` away from a number's cells is
+    /// within reach.
+    fn contains(self, rows: usize, columns: usize) -> bool {
+        match self {
+            Neighbourhood::Four => rows + columns <= 1,
+            Neighbourhood::Eight => rows.max(columns) <= 1,
+            Neighbourhood::Radius(radius) => rows.max(columns) <= radius,
+        }
+    }
+}
+
+/// The cells of a `width` x `height` grid within `neighbourhood` of the
+/// cells `start..start + len` on `row`.
+pub fn around(
+    neighbourhood: Neighbourhood,
+    width: usize,
+    height: usize,
+    row: usize,
+    start: usize,
+    len: usize,
+) -> impl Iterator<Item = Point> {
+    let reach = neighbourhood.reach();
+    let end = start + len - 1;
+    let rows = row.saturating_sub(reach)..(row + reach + 1).min(height);
+    let columns = start.saturating_sub(reach)..(end + reach + 1).min(width);
+
+    rows.flat_map(move |near_row| {
+        columns.clone().filter_map(move |column| {
+            let rows_away = near_row.abs_diff(row);
+            let columns_away = start.saturating_sub(column).max(column.saturating_sub(end));
+            neighbourhood
+                .contains(rows_away, columns_away)
+                .then_some(Point {
+                    row: near_row,
+                    column,
+                })
+        })
+    })
+}
+
+/// Occupancy grid over a schematic recording which number, if any, covers
+/// each cell. Adjacency checks then only look at the cells around a number
+/// or symbol instead of every other item.
+#[derive(Debug)]
+pub struct Index<'a> {
+    schematic: &'a Schematic,
+    number_at: Vec<u32>,
+    neighbourhood: Neighbourhood,
+}
+
+impl<'a> Index<'a> {
+    pub fn new(schematic: &'a Schematic, neighbourhood: Neighbourhood) -> Index<'a> {
+        let mut number_at = vec![EMPTY; schematic.cells.len()];
+
+        for (id, number) in schematic.numbers.iter().enumerate() {
+            for point in number.points() {
+                number_at[point.row * schematic.width + point.column] = id as u32;
+            }
+        }
+        Index {
+            schematic,
+            number_at,
+            neighbourhood,
+        }
+    }
+
+    pub fn schematic(&self) -> &'a Schematic {
+        self.schematic
+    }
+
+    fn around(&self, row: usize, start: usize, len: usize) -> impl Iterator<Item = Point> + '_ {
+        let (width, height) = (self.schematic.width, self.schematic.height());
+        around(self.neighbourhood, width, height, row, start, len)
+    }
+
+    fn cell(&self, point: Point) -> char {
+        self.schematic.cells[point.row * self.schematic.width + point.column]
+    }
+
+    /// Whether any symbol touches `number`.
+    pub fn is_part(&self, number: &Number) -> bool {
+        self.around(number.start.row, number.start.column, number.len)
+            .any(|point| schematic::is_symbol(self.cell(point)))
+    }
+
+    /// Indices into `schematic.numbers` of the numbers touching `point`.
+    pub fn adjacent_numbers(&self, point: Point) -> Vec<usize> {
+        let mut ids: Vec<usize> = Vec::new();
+
+        for near in self.around(point.row, point.column, 1) {
+            let id = self.number_at[near.row * self.schematic.width + near.column];
+            if id != EMPTY && !ids.contains(&(id as usize)) {
+                ids.push(id as usize);
+            }
+        }
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::*;
+    use crate::{prob_one, prob_two};
+
+    const DIAGONAL: &str = "\
+12....
+..*...
+...34.
+......
+5....#
+";
+
+    fn totals(neighbourhood: Neighbourhood) -> (u64, u64) {
+        let schematic = Schematic::parse(DIAGONAL).unwrap();
+        (
+            prob_one::problem_one(&schematic, neighbourhood),
+            prob_two::problem_two(&schematic, neighbourhood),
+        )
+    }
+
+    #[test]
+    fn four_connected() {
+        assert_eq!(totals(Neighbourhood::Four), (0, 0));
+    }
+
+    #[test]
+    fn eight_connected() {
+        assert_eq!(totals(Neighbourhood::Eight), (12 + 34, 12 * 34));
+        assert_eq!(
+            totals(Neighbourhood::Radius(1)),
+            totals(Neighbourhood::Eight)
+        );
+    }
+
+    #[test]
+    fn radius() {
+        assert_eq!(totals(Neighbourhood::Radius(2)), (12 + 34, 12 * 34));
+        assert_eq!(totals(Neighbourhood::Radius(3)), (12 + 34 + 5, 0));
+        assert_eq!(totals(Neighbourhood::Radius(0)), (0, 0));
+    }
+
+    #[test]
+    fn four_connected_edges() {
+        let schematic = Schematic::parse("..7..\n.3*..\n..1..\n").unwrap();
+        let index = Index::new(&schematic, Neighbourhood::Four);
+        let symbol = schematic.symbols[0].symbol_point;
+
+        assert_eq!(index.adjacent_numbers(symbol).len(), 3);
+        assert_eq!(prob_one::problem_one(&schematic, Neighbourhood::Four), 11);
+    }
+}
@@ -1,14 +1,259 @@
+mod bench;
+mod edit;
+mod index;
 mod prob_one;
 mod prob_two;
+mod query;
+mod render;
+mod schematic;
+#[cfg(test)]
+mod testing;
+
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::process;
+
+use edit::EditableSchematic;
+use index::{Index, Neighbourhood};
+use query::{Aggregate, Count, SymbolQuery};
+use render::Window;
+use schematic::{Point, Schematic};
+
+const USAGE: &str = "usage: day_03 [--adjacency four|eight|RADIUS] [--dump] [PATH]
+       day_03 [--adjacency ...] --symbols [--symbol C] [--exactly K | --at-least K] [--aggregate product|sum|max] [PATH]
+       day_03 [--adjacency ...] [--symbol C ...] --edits EDITS [--render] [PATH]
+       day_03 [--adjacency ...] --render [--window ROW0:ROW1,COL0:COL1] [PATH]
+       day_03 bench [SIZE]";
+
+struct Args {
+    path: Option<String>,
+    dump: bool,
+    bench: bool,
+    symbols: Option<SymbolQuery>,
+    neighbourhood: Neighbourhood,
+    render: bool,
+    window: Option<Window>,
+    edits: Option<String>,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut args = Args {
+        path: None,
+        dump: false,
+        bench: false,
+        symbols: None,
+        neighbourhood: Neighbourhood::default(),
+        render: false,
+        window: None,
+        edits: None,
+    };
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        let query = || SymbolQuery::default();
+        match arg.as_str() {
+            "--dump" => args.dump = true,
+            "--render" => args.render = true,
+            "--window" => {
+                args.render = true;
+                args.window = Some(iter.next()?.parse().ok()?);
+            }
+            "--edits" => args.edits = Some(iter.next()?),
+            "--adjacency" => {
+                args.neighbourhood = match iter.next()?.as_str() {
+                    "four" | "4" => Neighbourhood::Four,
+                    "eight" | "8" => Neighbourhood::Eight,
+                    radius => Neighbourhood::Radius(radius.parse().ok()?),
+                }
+            }
+            "--symbols" => {
+                args.symbols.get_or_insert_with(query);
+            }
+            "--symbol" => {
+                let text = iter.next()?;
+                let mut chars = text.chars();
+                let character = chars.next()?;
+                if chars.next().is_some() {
+                    return None;
+                }
+                args.symbols.get_or_insert_with(query).character = Some(character);
+            }
+            "--exactly" | "--at-least" => {
+                let k = iter.next()?.parse::<usize>().ok()?;
+                args.symbols.get_or_insert_with(query).count = match arg.as_str() {
+                    "--exactly" => Count::Exactly(k),
+                    _ => Count::AtLeast(k),
+                };
+            }
+            "--aggregate" => {
+                args.symbols.get_or_insert_with(query).aggregate = match iter.next()?.as_str() {
+                    "product" => Aggregate::Product,
+                    "sum" => Aggregate::Sum,
+                    "max" => Aggregate::Max,
+                    _ => return None,
+                };
+            }
+            "bench" if args.path.is_none() && !args.bench => args.bench = true,
+            _ if args.path.is_none() && !arg.starts_with("--") => args.path = Some(arg),
+            _ => return None,
+        }
+    }
+    Some(args)
+}
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("day_03: {err}");
+    process::exit(1);
+}
+
+fn run_symbols(schematic: &Schematic, query: &SymbolQuery, neighbourhood: Neighbourhood) {
+    let index = Index::new(schematic, neighbourhood);
+    let mut total: u64 = 0;
+
+    for found in query.select(&index) {
+        let numbers: Vec<String> = found
+            .numbers
+            .iter()
+            .map(|number| number.value.to_string())
+            .collect();
+        let value = found.value(query.aggregate);
+        total = total.saturating_add(value);
+
+        println!(
+            "row: {:}, column: {:} = {:}: [{}] -> {}",
+            found.symbol.symbol_point.row,
+            found.symbol.symbol_point.column,
+            found.symbol.character,
+            numbers.join(", "),
+            value
+        );
+    }
+    println!("total: {total}");
+}
+
+/// Applies one `ROW COLUMN CHAR` edit per line of `edits`, or `ROW COLUMN`
+/// to clear a cell, printing both sums after each.
+fn run_edits(editable: &mut EditableSchematic, edits: &str) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    for (line_number, line) in edits.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (row, column, character) = match fields[..] {
+            [] => continue,
+            [row, column] => (row, column, None),
+            [row, column, character] if character.chars().count() == 1 => {
+                (row, column, character.chars().next())
+            }
+            _ => fail(format!(
+                "edits line {}: expected ROW COLUMN [CHAR]",
+                line_number + 1
+            )),
+        };
+        let (Ok(row), Ok(column)) = (row.parse(), column.parse()) else {
+            fail(format!("edits line {}: bad cell", line_number + 1));
+        };
+
+        let point = Point { row, column };
+        match character {
+            Some(character) => editable.set(point, character),
+            None => editable.clear(point),
+        }
+        .unwrap_or_else(|err| fail(format!("edits line {}: {err}", line_number + 1)));
+        writeln!(
+            out,
+            "{} {} {}: {} {}",
+            row,
+            column,
+            character.unwrap_or('.'),
+            editable.part_sum(),
+            editable.gear_sum()
+        )?;
+    }
+    out.flush()
+}
 
 fn main() {
-    let sample = include_str!("../input/prob_one_input.txt");
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    };
+
+    if args.bench {
+        let size = match args.path.as_deref().map(str::parse::<usize>) {
+            None => 2_000,
+            Some(Ok(size)) => size,
+            Some(Err(err)) => fail(err),
+        };
+        return bench::run(size).unwrap_or_else(|err| fail(err));
+    }
+
+    let sample = match &args.path {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|err| fail(err)),
+        None => include_str!("../input/prob_one_input.txt").to_string(),
+    };
+    let mut schematic = Schematic::parse(&sample).unwrap_or_else(|err| fail(err));
 
-    println!("{:}", prob_one::problem_one(sample));
+    if let Some(path) = &args.edits {
+        let edits = fs::read_to_string(path).unwrap_or_else(|err| fail(err));
+        let gears = args.symbols.unwrap_or_else(SymbolQuery::gears);
+        let mut editable = EditableSchematic::new(&schematic, args.neighbourhood, gears);
+        run_edits(&mut editable, &edits).unwrap_or_else(|err| fail(err));
+        if !args.render {
+            return;
+        }
+        schematic = editable.to_schematic();
+    }
+    if args.dump {
+        return schematic.dump();
+    }
+    if args.render {
+        let index = Index::new(&schematic, args.neighbourhood);
+        let mut out = BufWriter::new(io::stdout().lock());
+        return render::render(&mut out, &index, args.window.as_ref())
+            .and_then(|_| out.flush())
+            .unwrap_or_else(|err| fail(err));
+    }
+    if let Some(query) = &args.symbols {
+        return run_symbols(&schematic, query, args.neighbourhood);
+    }
+
+    println!("{:}", prob_one::problem_one(&schematic, args.neighbourhood));
 
     println!("=============");
 
-    let sample_2 = include_str!("../input/prob_one_input.txt");
+    println!("{:}", prob_two::problem_two(&schematic, args.neighbourhood));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn sample_totals() {
+        let schematic = Schematic::parse(include_str!("../input/input_sample_one.txt")).unwrap();
+
+        assert_eq!(
+            prob_one::problem_one(&schematic, Neighbourhood::Eight),
+            4361
+        );
+        assert_eq!(
+            prob_two::problem_two(&schematic, Neighbourhood::Eight),
+            467835
+        );
+    }
+
+    #[test]
+    fn index_agrees_with_naive_scan() {
+        let schematic = Schematic::parse(&bench::synthetic(120)).unwrap();
 
-    println!("{:}", prob_two::problem_two(sample_2));
+        assert_eq!(
+            prob_one::problem_one(&schematic, Neighbourhood::Eight),
+            bench::naive_problem_one(&schematic)
+        );
+        assert_eq!(
+            prob_two::problem_two(&schematic, Neighbourhood::Eight),
+            bench::naive_problem_two(&schematic)
+        );
+    }
 }
@@ -0,0 +1,170 @@
+use crate::index::Index;
+use crate::schematic::{Number, Symbol};
+
+/// How many numbers a symbol must touch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    Any,
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+/// How the numbers touching one symbol combine into its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+/// A symbol and every number touching it.
+#[derive(Debug, Clone)]
+pub struct SymbolMatch<'a> {
+    pub symbol: &'a Symbol,
+    pub numbers: Vec<&'a Number>,
+}
+
+/// Selects symbols by character and by how many numbers touch them, then
+/// folds each symbol's numbers into one value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolQuery {
+    pub character: Option<char>,
+    pub count: Count,
+    pub aggregate: Aggregate,
+}
+
+impl Default for SymbolQuery {
+    fn default() -> SymbolQuery {
+        SymbolQuery {
+            character: None,
+            count: Count::Any,
+            aggregate: Aggregate::Product,
+        }
+    }
+}
+
+impl Count {
+    fn allows(self, count: usize) -> bool {
+        match self {
+            Count::Any => true,
+            Count::Exactly(k) => count == k,
+            Count::AtLeast(k) => count >= k,
+        }
+    }
+}
+
+impl SymbolMatch<'_> {
+    /// The aggregate of the touching numbers, 0 when there are none. A
+    /// product or sum too large for `u64` saturates.
+    pub fn value(&self, aggregate: Aggregate) -> u64 {
+        let values = self.numbers.iter().map(|number| number.value);
+        if self.numbers.is_empty() {
+            return 0;
+        }
+
+        match aggregate {
+            Aggregate::Product => values.fold(1, u64::saturating_mul),
+            Aggregate::Sum => values.fold(0, u64::saturating_add),
+            Aggregate::Max => values.max().unwrap(),
+        }
+    }
+}
+
+/// Every symbol of the schematic with the numbers touching it, in reading
+/// order.
+pub fn symbols_with_adjacent<'a>(index: &Index<'a>) -> Vec<SymbolMatch<'a>> {
+    let schematic = index.schematic();
+
+    schematic
+        .symbols
+        .iter()
+        .map(|symbol| SymbolMatch {
+            symbol,
+            numbers: index
+                .adjacent_numbers(symbol.symbol_point)
+                .into_iter()
+                .map(|id| &schematic.numbers[id])
+                .collect(),
+        })
+        .collect()
+}
+
+impl SymbolQuery {
+    /// Part two: a `*` touching exactly two numbers, multiplied.
+    pub fn gears() -> SymbolQuery {
+        SymbolQuery {
+            character: Some('*'),
+            count: Count::Exactly(2),
+            aggregate: Aggregate::Product,
+        }
+    }
+
+    pub fn matches(&self, found: &SymbolMatch) -> bool {
+        self.character
+            .is_none_or(|character| found.symbol.character == character)
+            && self.count.allows(found.numbers.len())
+    }
+
+    pub fn select<'a>(&self, index: &Index<'a>) -> Vec<SymbolMatch<'a>> {
+        symbols_with_adjacent(index)
+            .into_iter()
+            .filter(|found| self.matches(found))
+            .collect()
+    }
+
+    /// The sum of the aggregate over every selected symbol, saturating like
+    /// the aggregate itself.
+    pub fn total(&self, index: &Index) -> u64 {
+        self.select(index)
+            .iter()
+            .map(|found| found.value(self.aggregate))
+            .fold(0, u64::saturating_add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::*;
+    use crate::schematic::Schematic;
+
+    #[test]
+    fn rules_without_code_changes() {
+        let schematic = Schematic::parse(include_str!("../input/input_sample_one.txt")).unwrap();
+        let index = Index::new(&schematic, Default::default());
+        let total = |character, count, aggregate| {
+            SymbolQuery {
+                character,
+                count,
+                aggregate,
+            }
+            .total(&index)
+        };
+
+        assert_eq!(SymbolQuery::gears().total(&index), 467835);
+        assert_eq!(
+            total(Some('*'), Count::AtLeast(1), Aggregate::Sum),
+            467 + 35 + 617 + 755 + 598
+        );
+        assert_eq!(total(Some('#'), Count::Exactly(1), Aggregate::Max), 633);
+        assert_eq!(total(Some('#'), Count::Exactly(3), Aggregate::Sum), 0);
+        assert_eq!(
+            total(None, Count::Any, Aggregate::Max),
+            467 + 633 + 617 + 592 + 664 + 755
+        );
+        assert_eq!(symbols_with_adjacent(&index).len(), 6);
+    }
+
+    #[test]
+    fn totals_saturate() {
+        let row = "9999999999*9999999999";
+        let schematic = Schematic::parse(&format!("{row}\n{}\n{row}\n", ".".repeat(21))).unwrap();
+        let index = Index::new(&schematic, Default::default());
+        let sum = SymbolQuery {
+            aggregate: Aggregate::Sum,
+            ..SymbolQuery::gears()
+        };
+
+        assert_eq!(SymbolQuery::gears().total(&index), u64::MAX);
+        assert_eq!(sum.total(&index), 4 * 9999999999);
+    }
+}
@@ -0,0 +1,165 @@
+use std::io::{self, Write};
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::index::Index;
+use crate::query::SymbolQuery;
+use crate::schematic::Point;
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Part,
+    NotPart,
+    Symbol,
+    Gear,
+    GearNumber,
+}
+
+impl Style {
+    fn code(self) -> &'static str {
+        match self {
+            Style::Plain => "\x1b[2m",
+            Style::Part => "\x1b[32m",
+            Style::NotPart => "\x1b[31m",
+            Style::Symbol => "\x1b[1m",
+            Style::Gear => "\x1b[1;30;43m",
+            Style::GearNumber => "\x1b[1;33m",
+        }
+    }
+}
+
+/// The rows and columns to print, half-open like a range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Window {
+    pub rows: Range<usize>,
+    pub columns: Range<usize>,
+}
+
+impl FromStr for Window {
+    type Err = String;
+
+    /// Parses `ROW0:ROW1,COL0:COL1`. Either end of a range may be left out.
+    fn from_str(text: &str) -> Result<Window, String> {
+        let range = |text: &str| -> Result<Range<usize>, String> {
+            let (start, end) = text
+                .split_once(':')
+                .ok_or_else(|| format!("expected `START:END`, found {text:?}"))?;
+            let bound = |bound: &str, default: usize| match bound.trim() {
+                "" => Ok(default),
+                bound => bound
+                    .parse::<usize>()
+                    .map_err(|err| format!("{bound:?}: {err}")),
+            };
+            Ok(bound(start, 0)?..bound(end, usize::MAX)?)
+        };
+
+        let (rows, columns) = text
+            .split_once(',')
+            .ok_or_else(|| format!("expected `ROW0:ROW1,COL0:COL1`, found {text:?}"))?;
+        Ok(Window {
+            rows: range(rows)?,
+            columns: range(columns)?,
+        })
+    }
+}
+
+/// Prints the schematic with ANSI colours: part numbers green, other numbers
+/// red, gears and their two numbers highlighted, dots dimmed. Only the cells
+/// inside `window` are printed.
+pub fn render<W: Write>(out: &mut W, index: &Index, window: Option<&Window>) -> io::Result<()> {
+    let schematic = index.schematic();
+    let height = schematic.height();
+    let (rows, columns) = match window {
+        Some(window) => (
+            window.rows.start.min(height)..window.rows.end.min(height),
+            window.columns.start.min(schematic.width)..window.columns.end.min(schematic.width),
+        ),
+        None => (0..height, 0..schematic.width),
+    };
+    if rows.is_empty() || columns.is_empty() {
+        return Ok(());
+    }
+
+    let width = columns.len();
+    let mut styles = vec![Style::Plain; rows.len() * width];
+    let mut set = |point: Point, style: Style| {
+        if rows.contains(&point.row) && columns.contains(&point.column) {
+            styles[(point.row - rows.start) * width + point.column - columns.start] = style;
+        }
+    };
+
+    for number in &schematic.numbers {
+        let style = if index.is_part(number) {
+            Style::Part
+        } else {
+            Style::NotPart
+        };
+        number.points().for_each(|point| set(point, style));
+    }
+    for symbol in &schematic.symbols {
+        set(symbol.symbol_point, Style::Symbol);
+    }
+    for gear in SymbolQuery::gears().select(index) {
+        set(gear.symbol.symbol_point, Style::Gear);
+        for number in gear.numbers {
+            number
+                .points()
+                .for_each(|point| set(point, Style::GearNumber));
+        }
+    }
+
+    for row in rows.clone() {
+        let mut current: Option<Style> = None;
+        for column in columns.clone() {
+            let style = styles[(row - rows.start) * width + column - columns.start];
+            if current != Some(style) {
+                write!(out, "{}{}", RESET, style.code())?;
+                current = Some(style);
+            }
+            write!(out, "{}", schematic.cells[row * schematic.width + column])?;
+        }
+        writeln!(out, "{RESET}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::Neighbourhood;
+    use crate::render::*;
+    use crate::schematic::Schematic;
+
+    #[test]
+    fn crops_and_colours() {
+        let schematic = Schematic::parse(include_str!("../input/input_sample_one.txt")).unwrap();
+        let index = Index::new(&schematic, Neighbourhood::Eight);
+        let window: Window = "0:2,0:6".parse().unwrap();
+
+        let mut out = Vec::new();
+        render(&mut out, &index, Some(&window)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "\x1b[0m\x1b[1;33m467\x1b[0m\x1b[2m..\x1b[0m\x1b[31m1\x1b[0m"
+        );
+        assert_eq!(
+            lines[1],
+            "\x1b[0m\x1b[2m...\x1b[0m\x1b[1;30;43m*\x1b[0m\x1b[2m..\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn parses_open_windows() {
+        let window: Window = ":5,3:".parse().unwrap();
+
+        assert_eq!(window.rows, 0..5);
+        assert_eq!(window.columns, 3..usize::MAX);
+        assert!("3:5".parse::<Window>().is_err());
+    }
+}
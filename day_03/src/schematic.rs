@@ -0,0 +1,249 @@
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub symbol_point: Point,
+    pub character: char,
+}
+
+/// A run of digits on one row, starting at `start` and `len` cells long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub start: Point,
+    pub len: usize,
+    pub value: u64,
+}
+
+impl Number {
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (self.start.column..self.start.column + self.len).map(|column| Point {
+            row: self.start.row,
+            column,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchematicError {
+    Empty,
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl fmt::Display for SchematicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchematicError::Empty => write!(f, "schematic has no rows"),
+            SchematicError::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "row {} is {} columns wide, expected {}",
+                row + 1,
+                width,
+                expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchematicError {}
+
+/// The engine schematic as a rectangular grid. The width is taken from the
+/// first row and every other row must match it.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub width: usize,
+    pub cells: Vec<char>,
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+}
+
+pub fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+fn dump_symbol_vector(symbol_vec: &[Symbol]) {
+    for i in symbol_vec {
+        println!(
+            "row: {:}, column: {:} = {:}",
+            i.symbol_point.row, i.symbol_point.column, i.character
+        );
+    }
+}
+
+fn dump_number_vector(number_vec: &[Number]) {
+    for number in number_vec {
+        println!(
+            "row: {:}, column: {:}, len: {:} = {:}",
+            number.start.row, number.start.column, number.len, number.value
+        );
+    }
+}
+
+impl Schematic {
+    /// Parses `\n` or `\r\n` separated rows. A trailing newline is allowed.
+    pub fn parse(lines: &str) -> Result<Schematic, SchematicError> {
+        let rows: Vec<&str> = lines.lines().collect();
+        let width = rows.first().ok_or(SchematicError::Empty)?.chars().count();
+
+        let mut cells: Vec<char> = Vec::with_capacity(width * rows.len());
+        for (row, line) in rows.iter().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars());
+            if cells.len() - before != width {
+                return Err(SchematicError::Ragged {
+                    row,
+                    width: cells.len() - before,
+                    expected: width,
+                });
+            }
+        }
+        if width == 0 {
+            return Err(SchematicError::Empty);
+        }
+
+        let mut schematic = Schematic {
+            width,
+            cells,
+            numbers: Vec::new(),
+            symbols: Vec::new(),
+        };
+        schematic.numbers = schematic.parse_numbers();
+        schematic.symbols = schematic.parse_symbols();
+        Ok(schematic)
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    pub fn dump(&self) {
+        dump_symbol_vector(&self.symbols);
+        dump_number_vector(&self.numbers);
+    }
+
+    fn point(&self, pos: usize) -> Point {
+        Point {
+            row: pos / self.width,
+            column: pos % self.width,
+        }
+    }
+
+    fn parse_symbols(&self) -> Vec<Symbol> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| is_symbol(**c))
+            .map(|(pos, c)| Symbol {
+                symbol_point: self.point(pos),
+                character: *c,
+            })
+            .collect()
+    }
+
+    fn parse_numbers(&self) -> Vec<Number> {
+        self.cells
+            .chunks(self.width)
+            .enumerate()
+            .flat_map(|(row, line)| scan_numbers(line, row, 0..self.width))
+            .collect()
+    }
+}
+
+/// The digit runs of `line` (row `row`) that start within `columns`. A run
+/// may continue past `columns.end`. A run too long for `u64` saturates at
+/// `u64::MAX`.
+pub fn scan_numbers(line: &[char], row: usize, columns: Range<usize>) -> Vec<Number> {
+    let mut number_vec: Vec<Number> = Vec::new();
+    let mut column = columns.start;
+
+    while column < columns.end {
+        if !line[column].is_ascii_digit() {
+            column += 1;
+            continue;
+        }
+
+        let start = column;
+        let mut value: u64 = 0;
+        while column < line.len() && line[column].is_ascii_digit() {
+            value = value
+                .saturating_mul(10)
+                .saturating_add(line[column].to_digit(10).unwrap() as u64);
+            column += 1;
+        }
+        number_vec.push(Number {
+            start: Point { row, column: start },
+            len: column - start,
+            value,
+        });
+    }
+    number_vec
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schematic::*;
+
+    #[test]
+    fn infers_width_from_sample() {
+        let schematic = Schematic::parse(include_str!("../input/input_sample_one.txt")).unwrap();
+
+        assert_eq!((schematic.width, schematic.cells.len()), (10, 100));
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+        assert_eq!(
+            schematic.symbols[1].symbol_point,
+            Point { row: 3, column: 6 }
+        );
+    }
+
+    #[test]
+    fn numbers_stop_at_row_end() {
+        let schematic = Schematic::parse("..12\n34..\r\n").unwrap();
+        let values: Vec<u64> = schematic
+            .numbers
+            .iter()
+            .map(|number| number.value)
+            .collect();
+
+        assert_eq!(values, [12, 34]);
+    }
+
+    #[test]
+    fn long_runs_saturate() {
+        let schematic = Schematic::parse("99999999999999999999*18446744073709551615").unwrap();
+        let values: Vec<u64> = schematic
+            .numbers
+            .iter()
+            .map(|number| number.value)
+            .collect();
+
+        assert_eq!(values, [u64::MAX, u64::MAX]);
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_eq!(
+            Schematic::parse("....\n...\n....").unwrap_err(),
+            SchematicError::Ragged {
+                row: 1,
+                width: 3,
+                expected: 4
+            }
+        );
+        assert_eq!(Schematic::parse("").unwrap_err(), SchematicError::Empty);
+    }
+}
@@ -0,0 +1,7 @@
+/// xorshift64, so failures reproduce.
+pub fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
@@ -0,0 +1,78 @@
+use std::time::Instant;
+
+use crate::cascade;
+
+/// Above this many cards the quadratic cascade is skipped.
+const NAIVE_LIMIT: usize = 20_000;
+
+/// The cascade before direct indexing: every win looks its cards up by
+/// number.
+pub fn naive_cascade(wins: &[usize]) -> usize {
+    let mut cards: Vec<(usize, usize)> = (1..wins.len() + 1).map(|index| (index, 1)).collect();
+
+    for (game_num, &win) in wins.iter().enumerate() {
+        let correct_num = game_num + 1;
+        for win_count in (1..=win).rev() {
+            let cards_to_add = cards
+                .iter()
+                .find(|(index, _)| correct_num == *index)
+                .unwrap()
+                .1;
+            cards
+                .iter_mut()
+                .find(|(index, _)| correct_num + win_count == *index)
+                .unwrap()
+                .1 += cards_to_add;
+        }
+    }
+    cards.iter().map(|(_, total)| total).sum()
+}
+
+/// xorshift64, so the synthetic cards are the same on every run.
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Wins for `count` cards: mostly none, sometimes one or two, so copy counts
+/// stay well within a `usize`. No card wins past the last.
+pub fn synthetic(count: usize) -> Vec<usize> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+    (0..count)
+        .map(|pos| {
+            let win = match next_random(&mut state) % 8 {
+                0 => 2,
+                1 | 2 => 1,
+                _ => 0,
+            };
+            win.min(count - pos - 1)
+        })
+        .collect()
+}
+
+fn time<T: std::fmt::Display>(name: &str, run: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = run();
+    println!("{name:>7}: {result} in {:?}", start.elapsed());
+    result
+}
+
+pub fn run(count: usize) -> Result<(), String> {
+    let wins = synthetic(count);
+
+    let start = Instant::now();
+    let linear = cascade::cascade(&wins).map_err(|err| err.to_string())?;
+    println!("{:>7}: {linear} in {:?}", "linear", start.elapsed());
+
+    if count > NAIVE_LIMIT {
+        println!("naive cascade skipped above {NAIVE_LIMIT} cards");
+        return Ok(());
+    }
+    if time("naive", || naive_cascade(&wins)) != linear {
+        return Err("naive and linear totals differ".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: usize,
+    pub winning: Vec<u64>,
+    pub have: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardError {
+    MissingHeader,
+    BadId(String),
+    MissingBar,
+    BadNumber(String),
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardError::MissingHeader => write!(f, "expected `Card <id>: ...`"),
+            CardError::BadId(id) => write!(f, "card id {id:?} is not a number"),
+            CardError::MissingBar => write!(f, "expected `<winning> | <have>`"),
+            CardError::BadNumber(number) => write!(f, "{number:?} is not a number"),
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
+
+/// A [`CardError`] together with the line of the pile it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line_number: usize,
+    pub line: String,
+    pub error: CardError,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {}: {:?}",
+            self.line_number, self.error, self.line
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Card {
+    /// How many of the numbers we have are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.have
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count()
+    }
+}
+
+fn parse_numbers(text: &str) -> Result<Vec<u64>, CardError> {
+    text.split_whitespace()
+        .map(|number| {
+            number
+                .parse::<u64>()
+                .map_err(|_| CardError::BadNumber(number.to_string()))
+        })
+        .collect()
+}
+
+impl FromStr for Card {
+    type Err = CardError;
+
+    fn from_str(line: &str) -> Result<Card, CardError> {
+        let (header, numbers) = line.split_once(':').ok_or(CardError::MissingHeader)?;
+        let id = header
+            .trim()
+            .strip_prefix("Card")
+            .ok_or(CardError::MissingHeader)?
+            .trim();
+        let id = id
+            .parse::<usize>()
+            .map_err(|_| CardError::BadId(id.to_string()))?;
+
+        let (winning, have) = numbers.split_once('|').ok_or(CardError::MissingBar)?;
+        Ok(Card {
+            id,
+            winning: parse_numbers(winning)?,
+            have: parse_numbers(have)?,
+        })
+    }
+}
+
+/// Parses one card per non-empty line, in file order.
+pub fn parse_cards(file: &str) -> Result<Vec<Card>, ParseError> {
+    file.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.parse::<Card>().map_err(|error| ParseError {
+                line_number: index + 1,
+                line: line.to_string(),
+                error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::card::*;
+
+    #[test]
+    fn keeps_the_id() {
+        let card: Card = "Card  17: 41 48 83 | 83 86  6 48".parse().unwrap();
+
+        assert_eq!(card.id, 17);
+        assert_eq!(card.winning, [41, 48, 83]);
+        assert_eq!(card.have, [83, 86, 6, 48]);
+        assert_eq!(card.matches(), 2);
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let bad = |line: &str| line.parse::<Card>().unwrap_err();
+
+        assert_eq!(bad("41 48 | 83"), CardError::MissingHeader);
+        assert_eq!(bad("Card x: 41 | 83"), CardError::BadId("x".to_string()));
+        assert_eq!(bad("Card 1: 41 48 83"), CardError::MissingBar);
+        assert_eq!(
+            bad("Card 1: 41 4a | 83"),
+            CardError::BadNumber("4a".to_string())
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use crate::card::Card;
+use crate::scoring::Scoring;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CascadeError {
+    /// The copies of card `card` no longer fit in a `usize`.
+    Overflow {
+        card: usize,
+    },
+    /// Card `card` wins copies of cards after the last one.
+    PastEnd {
+        card: usize,
+        wins: usize,
+    },
+    /// Card `card` wins a copy of card `target`, which is not in the pile.
+    MissingCard {
+        card: usize,
+        target: usize,
+    },
+    DuplicateCard {
+        card: usize,
+    },
+}
+
+impl fmt::Display for CascadeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CascadeError::Overflow { card } => {
+                write!(f, "copies of card {card} overflow a usize")
+            }
+            CascadeError::PastEnd { card, wins } => {
+                write!(f, "card {card} wins {wins} cards, past the last card")
+            }
+            CascadeError::MissingCard { card, target } => {
+                write!(
+                    f,
+                    "card {card} wins a copy of card {target}, which is missing"
+                )
+            }
+            CascadeError::DuplicateCard { card } => write!(f, "card {card} appears twice"),
+        }
+    }
+}
+
+impl std::error::Error for CascadeError {}
+
+/// The total number of scratchcards held once every win has been cashed in,
+/// where `wins[i]` is the number of matches on the `i`th card.
+///
+/// Each card hands its copies to a run of the following cards, so instead of
+/// visiting every card in the run it adds its copies to a running count and
+/// records where they stop applying. That keeps the cascade linear in the
+/// number of cards whatever the wins are.
+pub fn cascade(wins: &[usize]) -> Result<usize, CascadeError> {
+    spread(wins, |pos| pos + 1)
+}
+
+/// [`cascade`] over cards in any order and with gaps in their ids. Card `n`
+/// with `w` matches wins one copy each of cards `n + 1` to `n + w`, all of
+/// which must be in the pile, where `w` is what `scoring` makes of the
+/// matches.
+pub fn cascade_cards(cards: &[Card], scoring: &dyn Scoring) -> Result<usize, CascadeError> {
+    let (order, wins) = in_order(cards, scoring)?;
+    spread(&wins, |pos| order[pos].id)
+}
+
+/// `cards` sorted by id alongside their wins, once every win is known to
+/// land on a card of the pile. The wins of a card then go to the cards right
+/// after it.
+pub fn in_order<'a>(
+    cards: &'a [Card],
+    scoring: &dyn Scoring,
+) -> Result<(Vec<&'a Card>, Vec<usize>), CascadeError> {
+    let mut order: Vec<&Card> = cards.iter().collect();
+    order.sort_by_key(|card| card.id);
+    if let Some(pair) = order.windows(2).find(|pair| pair[0].id == pair[1].id) {
+        return Err(CascadeError::DuplicateCard { card: pair[0].id });
+    }
+
+    // With the ids sorted and distinct, the targets of a card are exactly
+    // the next `win` cards when the last of them has the expected id.
+    let wins: Vec<usize> = order
+        .iter()
+        .map(|card| scoring.wins(card.matches()))
+        .collect();
+    let last_id = order.last().map_or(0, |card| card.id);
+    for (pos, (card, &win)) in order.iter().zip(&wins).enumerate() {
+        let target_id = |offset: usize| card.id.saturating_add(offset);
+        if win == 0 || order.get(pos + win).map(|target| target.id) == Some(target_id(win)) {
+            continue;
+        }
+        if target_id(win) > last_id {
+            return Err(CascadeError::PastEnd {
+                card: card.id,
+                wins: win,
+            });
+        }
+        let missing = (1..=win)
+            .find(|&offset| {
+                order.get(pos + offset).map(|target| target.id) != Some(target_id(offset))
+            })
+            .unwrap();
+        return Err(CascadeError::MissingCard {
+            card: card.id,
+            target: target_id(missing),
+        });
+    }
+    Ok((order, wins))
+}
+
+/// The positional cascade, reporting errors against `id(pos)`.
+fn spread(wins: &[usize], id: impl Fn(usize) -> usize) -> Result<usize, CascadeError> {
+    let count = wins.len();
+    // Copies that stop applying at each card, to be taken off `carried`.
+    let mut expiring = vec![0_usize; count + 1];
+    // Copies won by earlier cards that apply to the current one.
+    let mut carried: usize = 0;
+    let mut total: usize = 0;
+
+    for (pos, &win) in wins.iter().enumerate() {
+        let overflow = CascadeError::Overflow { card: id(pos) };
+        carried -= expiring[pos];
+        let copies = carried.checked_add(1).ok_or(overflow.clone())?;
+        total = total.checked_add(copies).ok_or(overflow.clone())?;
+
+        if win == 0 {
+            continue;
+        }
+        if win >= count - pos {
+            return Err(CascadeError::PastEnd {
+                card: id(pos),
+                wins: win,
+            });
+        }
+        carried = carried.checked_add(copies).ok_or(overflow.clone())?;
+        expiring[pos + win + 1] = expiring[pos + win + 1]
+            .checked_add(copies)
+            .ok_or(overflow)?;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::card::parse_cards;
+    use crate::cascade::*;
+    use crate::scoring::Doubling;
+
+    #[test]
+    fn sample() {
+        assert_eq!(cascade(&[4, 2, 2, 1, 0, 0]), Ok(30));
+    }
+
+    #[test]
+    fn wins_past_the_last_card() {
+        assert_eq!(
+            cascade(&[1, 1]),
+            Err(CascadeError::PastEnd { card: 2, wins: 1 })
+        );
+    }
+
+    #[test]
+    fn cards_out_of_order_and_sparse() {
+        let cards = parse_cards(
+            "Card 30: 1 | 2
+Card 10: 1 2 | 1 2
+Card 12: 1 | 2
+Card 11: 1 | 1
+Card 31: 1 | 1
+Card 32: 1 | 2
+",
+        )
+        .unwrap();
+        // 10 wins 11 and 12, 11 wins another 12; 31 wins 32.
+        assert_eq!(cascade_cards(&cards, &Doubling), Ok(1 + 2 + 4 + 1 + 1 + 2));
+    }
+
+    #[test]
+    fn reports_bad_targets() {
+        let cascade_text = |text: &str| cascade_cards(&parse_cards(text).unwrap(), &Doubling);
+
+        assert_eq!(
+            cascade_text("Card 1: 1 2 | 1 2\nCard 3: 1 | 2\n"),
+            Err(CascadeError::MissingCard { card: 1, target: 2 })
+        );
+        assert_eq!(
+            cascade_text("Card 1: 1 2 | 1 2\nCard 2: 1 | 2\n"),
+            Err(CascadeError::PastEnd { card: 1, wins: 2 })
+        );
+        assert_eq!(
+            cascade_text("Card 4: 1 | 2\nCard 4: 1 | 2\n"),
+            Err(CascadeError::DuplicateCard { card: 4 })
+        );
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        // Every card wins the next two, so copies grow like Fibonacci numbers.
+        let mut wins = vec![2; 200];
+        wins[198] = 1;
+        wins[199] = 0;
+        assert!(matches!(cascade(&wins), Err(CascadeError::Overflow { .. })));
+    }
+}
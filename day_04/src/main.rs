@@ -1,18 +1,122 @@
+mod bench;
+mod card;
+mod cascade;
 mod problem_one;
 mod problem_two;
+mod provenance;
+mod scoring;
+
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::process;
+
+use provenance::{Format, Provenance};
+use scoring::Scoring;
+
+const USAGE: &str = "usage: day_04 [--scoring doubling|linear|fibonacci|table:ENTRIES] [--provenance table|dot] [PATH]
+       day_04 bench [CARDS]";
+
+struct Args {
+    path: Option<String>,
+    bench: bool,
+    provenance: Option<Format>,
+    scoring: Box<dyn Scoring>,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut args = Args {
+        path: None,
+        bench: false,
+        provenance: None,
+        scoring: Box::new(scoring::Doubling),
+    };
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--provenance" => {
+                args.provenance = match iter.next()?.as_str() {
+                    "table" => Some(Format::Table),
+                    "dot" => Some(Format::Dot),
+                    _ => return None,
+                }
+            }
+            "--scoring" => {
+                args.scoring = scoring::parse(&iter.next()?).unwrap_or_else(|err| fail(err))
+            }
+            "bench" if args.path.is_none() && !args.bench => args.bench = true,
+            _ if args.path.is_none() && !arg.starts_with("--") => args.path = Some(arg),
+            _ => return None,
+        }
+    }
+    Some(args)
+}
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("day_04: {err}");
+    process::exit(1);
+}
 
 fn main() {
-    let file = include_str!("../input/prob_one_input.txt");
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    };
+
+    if args.bench {
+        let count = match args.path.as_deref().map(str::parse::<usize>) {
+            None => 1_000_000,
+            Some(Ok(count)) => count,
+            Some(Err(err)) => fail(err),
+        };
+        return bench::run(count).unwrap_or_else(|err| fail(err));
+    }
+
+    let file = match &args.path {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|err| fail(err)),
+        None => include_str!("../input/prob_one_input.txt").to_string(),
+    };
+    let cards = card::parse_cards(&file).unwrap_or_else(|err| fail(err));
+
+    if let Some(format) = args.provenance {
+        let provenance =
+            Provenance::build(&cards, args.scoring.as_ref()).unwrap_or_else(|err| fail(err));
+        let mut out = BufWriter::new(io::stdout().lock());
+        return provenance
+            .write(format, &mut out)
+            .and_then(|_| out.flush())
+            .unwrap_or_else(|err| fail(err));
+    }
 
     println!(
         "{:?}",
-        file.lines()
-            .map(|line| { problem_one::problem_one_sol(line) })
-            .sum::<u64>()
+        cards
+            .iter()
+            .map(|card| problem_one::problem_one_sol(card, args.scoring.as_ref()))
+            .fold(0, u64::saturating_add)
     );
     println!("=======");
 
-    let file2 = include_str!("../input/prob_one_input.txt");
+    let total =
+        problem_two::problem_two_sol(&cards, args.scoring.as_ref()).unwrap_or_else(|err| fail(err));
+    println!("{:?}", total);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn linear_cascade_agrees_with_naive() {
+        let sample = include_str!("../input/prob_one_sample.txt");
+        let cards = card::parse_cards(sample).unwrap();
+        assert_eq!(
+            problem_two::problem_two_sol(&cards, &scoring::Doubling),
+            Ok(30)
+        );
 
-    println!("{:?}", problem_two::problem_two_sol(file2));
+        let wins = bench::synthetic(2_000);
+        assert_eq!(cascade::cascade(&wins), Ok(bench::naive_cascade(&wins)));
+    }
 }
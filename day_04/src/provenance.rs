@@ -0,0 +1,174 @@
+use std::io::{self, Write};
+
+use crate::card::Card;
+use crate::cascade::{self, CascadeError};
+use crate::scoring::Scoring;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Dot,
+}
+
+/// Where the copies of one card came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCopies {
+    pub id: usize,
+    pub wins: usize,
+    /// Copies held once the cascade is over, the original included.
+    pub copies: usize,
+    /// Copies won from each earlier card as `(id, copies)`, in id order.
+    pub sources: Vec<(usize, usize)>,
+    /// Copies of later cards this card handed out: `wins` per copy held.
+    pub created: usize,
+}
+
+/// The cascade with every won copy traced back to the card that won it.
+///
+/// Every card held is either an original or was created by exactly one
+/// copy of an earlier card, so the total is the number of cards plus the
+/// sum of `created`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub cards: Vec<CardCopies>,
+    pub total: usize,
+}
+
+impl Provenance {
+    /// Runs the cascade over `cards`, recording one source per win. This is
+    /// linear in the total number of wins rather than in the number of
+    /// cards.
+    pub fn build(cards: &[Card], scoring: &dyn Scoring) -> Result<Provenance, CascadeError> {
+        let (order, wins) = cascade::in_order(cards, scoring)?;
+        let mut traced: Vec<CardCopies> = order
+            .iter()
+            .zip(&wins)
+            .map(|(card, &wins)| CardCopies {
+                id: card.id,
+                wins,
+                copies: 0,
+                sources: Vec::new(),
+                created: 0,
+            })
+            .collect();
+        let mut total: usize = 0;
+
+        for pos in 0..traced.len() {
+            let card = &mut traced[pos];
+            let overflow = CascadeError::Overflow { card: card.id };
+            card.copies = card
+                .sources
+                .iter()
+                .try_fold(1_usize, |sum, &(_, copies)| sum.checked_add(copies))
+                .ok_or(overflow.clone())?;
+            card.created = card.copies.checked_mul(card.wins).ok_or(overflow.clone())?;
+            total = total.checked_add(card.copies).ok_or(overflow)?;
+
+            let (id, copies, wins) = (card.id, card.copies, card.wins);
+            for target in &mut traced[pos + 1..=pos + wins] {
+                target.sources.push((id, copies));
+            }
+        }
+        Ok(Provenance {
+            cards: traced,
+            total,
+        })
+    }
+
+    pub fn write(&self, format: Format, out: &mut impl Write) -> io::Result<()> {
+        match format {
+            Format::Table => self.write_table(out),
+            Format::Dot => self.write_dot(out),
+        }
+    }
+
+    pub fn write_table(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "{:>6} {:>5} {:>8} {:>8}  from (card:copies)",
+            "card", "wins", "copies", "created"
+        )?;
+        for card in &self.cards {
+            let sources: Vec<String> = card
+                .sources
+                .iter()
+                .map(|(id, copies)| format!("{id}:{copies}"))
+                .collect();
+            writeln!(
+                out,
+                "{:>6} {:>5} {:>8} {:>8}  {}",
+                card.id,
+                card.wins,
+                card.copies,
+                card.created,
+                if sources.is_empty() {
+                    "-".to_string()
+                } else {
+                    sources.join(" ")
+                }
+            )?;
+        }
+        writeln!(
+            out,
+            "total: {} = {} originals + {} won",
+            self.total,
+            self.cards.len(),
+            self.total - self.cards.len()
+        )
+    }
+
+    /// One node per card labelled with its copies, and one edge per win
+    /// labelled with the copies it carried.
+    pub fn write_dot(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "digraph provenance {{")?;
+        writeln!(out, "    rankdir=LR;")?;
+        for card in &self.cards {
+            writeln!(
+                out,
+                "    card{} [label=\"Card {}\\n{} held\"];",
+                card.id, card.id, card.copies
+            )?;
+        }
+        for card in &self.cards {
+            for (source, copies) in &card.sources {
+                writeln!(
+                    out,
+                    "    card{} -> card{} [label=\"{}\"];",
+                    source, card.id, copies
+                )?;
+            }
+        }
+        writeln!(out, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::card::parse_cards;
+    use crate::provenance::*;
+    use crate::scoring::Doubling;
+
+    #[test]
+    fn traces_the_sample() {
+        let cards = parse_cards(include_str!("../input/prob_one_sample.txt")).unwrap();
+        let provenance = Provenance::build(&cards, &Doubling).unwrap();
+
+        assert_eq!(provenance.total, 30);
+        assert_eq!(provenance.cards[3].copies, 8);
+        assert_eq!(provenance.cards[3].sources, [(1, 1), (2, 2), (3, 4)]);
+        assert_eq!(
+            provenance.total,
+            cards.len()
+                + provenance
+                    .cards
+                    .iter()
+                    .map(|card| card.created)
+                    .sum::<usize>()
+        );
+
+        let mut dot = Vec::new();
+        provenance.write_dot(&mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.contains("card3 -> card4 [label=\"4\"];"));
+    }
+}
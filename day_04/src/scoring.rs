@@ -0,0 +1,173 @@
+use std::fmt;
+
+/// How a card with a given number of matches scores, and how many cards it
+/// wins in the copy cascade.
+pub trait Scoring {
+    /// Points for a card with `matches` winning numbers.
+    fn points(&self, matches: usize) -> u64;
+
+    /// Cards after this one won by a card with `matches` winning numbers.
+    fn wins(&self, matches: usize) -> usize {
+        matches
+    }
+}
+
+/// The puzzle's rule: 1 point for the first match, doubled for each one
+/// after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Doubling;
+
+/// 1 point per match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear;
+
+/// 1, 2, 3, 5, 8, ... points: each further match scores the last two
+/// scores added together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fibonacci;
+
+/// Points, and optionally wins, looked up by number of matches. Cards with
+/// more matches than the table covers use its last entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    entries: Vec<(u64, Option<usize>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoringError {
+    Unknown(String),
+    EmptyTable,
+    BadEntry(String),
+}
+
+impl fmt::Display for ScoringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScoringError::Unknown(name) => write!(
+                f,
+                "unknown scoring {name:?}, expected doubling, linear, fibonacci or table:..."
+            ),
+            ScoringError::EmptyTable => write!(f, "scoring table has no entries"),
+            ScoringError::BadEntry(entry) => write!(
+                f,
+                "scoring table entry {entry:?} is not `POINTS` or `POINTS/WINS`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScoringError {}
+
+impl Scoring for Doubling {
+    /// Saturates from 65 matches on.
+    fn points(&self, matches: usize) -> u64 {
+        match matches {
+            0 => 0,
+            matches => 1_u64.checked_shl(matches as u32 - 1).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+impl Scoring for Linear {
+    fn points(&self, matches: usize) -> u64 {
+        matches as u64
+    }
+}
+
+impl Scoring for Fibonacci {
+    /// Saturates once the sequence leaves `u64`.
+    fn points(&self, matches: usize) -> u64 {
+        if matches == 0 {
+            return 0;
+        }
+        let (mut last, mut points) = (0_u64, 1_u64);
+        for _ in 0..matches {
+            (last, points) = (points, points.saturating_add(last));
+        }
+        points
+    }
+}
+
+impl Table {
+    /// Parses comma separated entries, the first for no matches, each either
+    /// `POINTS` or `POINTS/WINS`: `0,1,3/1,7/3`.
+    pub fn parse(text: &str) -> Result<Table, ScoringError> {
+        if text.trim().is_empty() {
+            return Err(ScoringError::EmptyTable);
+        }
+        let entries = text
+            .split(',')
+            .map(|entry| {
+                let bad_entry = || ScoringError::BadEntry(entry.trim().to_string());
+                let (points, wins) = match entry.split_once('/') {
+                    Some((points, wins)) => (points, Some(wins)),
+                    None => (entry, None),
+                };
+                Ok((
+                    points.trim().parse().map_err(|_| bad_entry())?,
+                    wins.map(|wins| wins.trim().parse().map_err(|_| bad_entry()))
+                        .transpose()?,
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Table { entries })
+    }
+
+    fn entry(&self, matches: usize) -> (u64, Option<usize>) {
+        self.entries[matches.min(self.entries.len() - 1)]
+    }
+}
+
+impl Scoring for Table {
+    fn points(&self, matches: usize) -> u64 {
+        self.entry(matches).0
+    }
+
+    fn wins(&self, matches: usize) -> usize {
+        self.entry(matches).1.unwrap_or(matches)
+    }
+}
+
+/// `doubling`, `linear`, `fibonacci` or `table:ENTRIES`, see [`Table::parse`].
+pub fn parse(name: &str) -> Result<Box<dyn Scoring>, ScoringError> {
+    match name {
+        "doubling" => Ok(Box::new(Doubling)),
+        "linear" => Ok(Box::new(Linear)),
+        "fibonacci" => Ok(Box::new(Fibonacci)),
+        _ => match name.strip_prefix("table:") {
+            Some(entries) => Ok(Box::new(Table::parse(entries)?)),
+            None => Err(ScoringError::Unknown(name.to_string())),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoring::*;
+
+    fn first_points(scoring: &dyn Scoring) -> Vec<u64> {
+        (0..7).map(|matches| scoring.points(matches)).collect()
+    }
+
+    #[test]
+    fn built_in_schemes() {
+        assert_eq!(first_points(&Doubling), [0, 1, 2, 4, 8, 16, 32]);
+        assert_eq!(first_points(&Linear), [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(first_points(&Fibonacci), [0, 1, 2, 3, 5, 8, 13]);
+        assert_eq!(Doubling.points(64), 1 << 63);
+        assert_eq!(Doubling.points(65), u64::MAX);
+    }
+
+    #[test]
+    fn table_entries() {
+        let table = Table::parse("0, 1, 3/1, 7/0").unwrap();
+
+        assert_eq!(first_points(&table), [0, 1, 3, 7, 7, 7, 7]);
+        assert_eq!((table.wins(1), table.wins(2), table.wins(5)), (1, 1, 0));
+        assert_eq!(Table::parse(""), Err(ScoringError::EmptyTable));
+        assert_eq!(
+            Table::parse("0,x/1"),
+            Err(ScoringError::BadEntry("x/1".to_string()))
+        );
+    }
+}
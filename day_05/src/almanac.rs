@@ -0,0 +1,146 @@
+use crate::compose::Piecewise;
+use crate::graph::{self, RouteError};
+use crate::interval::{IntervalSet, Piece, END};
+
+#[derive(Debug)]
+pub struct Map {
+    /// The header line as written, such as `seed-to-soil map:`.
+    pub header: String,
+    pub header_line: usize,
+    /// The categories named by the header, empty when it does not read
+    /// `<source>-to-<destination> map:`.
+    pub source: String,
+    pub destination: String,
+    pub rows: Vec<(u64, u64, u64)>,
+    /// The line number of each of `rows`.
+    pub row_lines: Vec<usize>,
+    /// Lines in this section that are not three numbers, left out of `rows`.
+    pub bad_rows: Vec<usize>,
+}
+
+impl Map {
+    fn new(header: &str, header_line: usize) -> Map {
+        let (source, destination) = header
+            .strip_suffix(" map:")
+            .and_then(|names| names.split_once("-to-"))
+            .unwrap_or_default();
+        Map {
+            header: header.to_string(),
+            header_line,
+            source: source.to_string(),
+            destination: destination.to_string(),
+            rows: Vec::new(),
+            row_lines: Vec::new(),
+            bad_rows: Vec::new(),
+        }
+    }
+
+    /// The rows as pieces, in file order so the first match wins. A row
+    /// running past `u64::MAX` is cut off there.
+    fn pieces(&self) -> Vec<Piece> {
+        self.rows
+            .iter()
+            .map(|&(dest_range_start, source_range_start, range_len)| Piece {
+                source: source_range_start as u128
+                    ..(source_range_start as u128 + range_len as u128).min(END),
+                destination: dest_range_start,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub seeds_line: usize,
+    /// Tokens of the `seeds:` line that are not numbers, left out of `seeds`.
+    pub bad_seeds: Vec<String>,
+    pub maps: Vec<Map>,
+}
+
+impl Almanac {
+    /// The seed ranges of part two. A range running past `u64::MAX` is cut
+    /// off there.
+    pub fn seed_ranges(&self) -> IntervalSet {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| pair[0] as u128..(pair[0] as u128 + pair[1] as u128).min(END))
+            .collect()
+    }
+
+    /// The maps leading from category `from` to category `to`, in the
+    /// order they apply.
+    pub fn route(&self, from: &str, to: &str) -> Result<Vec<&Map>, RouteError> {
+        graph::route(&self.maps, from, to)
+    }
+
+    /// The maps on the route from `from` to `to` folded into one function.
+    pub fn mapping(&self, from: &str, to: &str) -> Result<Piecewise, RouteError> {
+        Ok(self
+            .route(from, to)?
+            .into_iter()
+            .fold(Piecewise::identity(), |f, map| {
+                f.then(&Piecewise::from_rows(&map.rows))
+            }))
+    }
+
+    /// Every map from seed to location folded into one function.
+    pub fn composed(&self) -> Result<Piecewise, RouteError> {
+        self.mapping("seed", "location")
+    }
+
+    /// The seed ranges, pushed through every map from seed to location as
+    /// whole ranges rather than one seed at a time.
+    pub fn locations(&self) -> Result<IntervalSet, RouteError> {
+        Ok(self
+            .route("seed", "location")?
+            .into_iter()
+            .fold(self.seed_ranges(), |ranges, map| {
+                ranges.apply(&map.pieces())
+            }))
+    }
+}
+
+/// Reads the `seeds:` line and every map, keeping the line numbers for
+/// [`crate::validate`]. Maps without rows are kept too, and malformed seeds
+/// and rows are set aside for the validator to report.
+pub fn parse(file: &str) -> Almanac {
+    let mut almanac = Almanac {
+        seeds: Vec::new(),
+        seeds_line: 0,
+        bad_seeds: Vec::new(),
+        maps: Vec::new(),
+    };
+
+    for (index, line) in file.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(seeds) = line.strip_prefix("seeds:") {
+            almanac.seeds_line = index + 1;
+            for num in seeds.split_whitespace() {
+                match num.parse::<u64>() {
+                    Ok(seed) => almanac.seeds.push(seed),
+                    Err(_) => almanac.bad_seeds.push(num.to_string()),
+                }
+            }
+        } else if line.ends_with(':') {
+            almanac.maps.push(Map::new(line, index + 1));
+        } else if let Some(map) = almanac.maps.last_mut() {
+            let num: Result<Vec<_>, _> = line
+                .split_whitespace()
+                .map(|num| num.parse::<u64>())
+                .collect();
+            match num.as_deref() {
+                Ok(&[destination, source, len]) => {
+                    map.rows.push((destination, source, len));
+                    map.row_lines.push(index + 1);
+                }
+                _ => map.bad_rows.push(index + 1),
+            }
+        }
+    }
+    almanac
+}
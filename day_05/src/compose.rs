@@ -0,0 +1,197 @@
+use crate::interval::{IntervalSet, END};
+
+/// Where a linear piece starts: `start + k` maps to `image + k` up to the
+/// start of the next piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: u64,
+    pub image: u64,
+}
+
+/// A function on all of `u64` built from slope-one pieces, such as one
+/// almanac map or several maps composed. The first segment starts at 0 and
+/// neighbouring segments never share an offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piecewise {
+    segments: Vec<Segment>,
+}
+
+impl Piecewise {
+    pub fn identity() -> Piecewise {
+        Piecewise {
+            segments: vec![Segment { start: 0, image: 0 }],
+        }
+    }
+
+    /// The function described by the `(destination, source, length)` rows
+    /// of one map. Where rows overlap the first one wins, as in a lookup;
+    /// values no row covers map to themselves.
+    pub fn from_rows(rows: &[(u64, u64, u64)]) -> Piecewise {
+        let mut breakpoints: Vec<u64> = vec![0];
+        for &(_, source, len) in rows {
+            breakpoints.push(source);
+            breakpoints.extend(source.checked_add(len));
+        }
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        let segments = breakpoints.into_iter().map(|start| {
+            let image = rows
+                .iter()
+                .find(|&&(_, source, len)| source <= start && start - source < len)
+                .map_or(start, |&(destination, source, _)| {
+                    destination.saturating_add(start - source)
+                });
+            Segment { start, image }
+        });
+        Piecewise::coalesce(segments)
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Drops segments that merely continue the one before them.
+    fn coalesce(segments: impl IntoIterator<Item = Segment>) -> Piecewise {
+        let offset = |segment: &Segment| segment.image as i128 - segment.start as i128;
+        let mut kept: Vec<Segment> = Vec::new();
+
+        for segment in segments {
+            if kept.last().map(offset) != Some(offset(&segment)) {
+                kept.push(segment);
+            }
+        }
+        Piecewise { segments: kept }
+    }
+
+    fn end(&self, pos: usize) -> u128 {
+        self.segments
+            .get(pos + 1)
+            .map_or(END, |segment| segment.start as u128)
+    }
+
+    fn position(&self, value: u64) -> usize {
+        self.segments
+            .partition_point(|segment| segment.start <= value)
+            - 1
+    }
+
+    pub fn apply(&self, value: u64) -> u64 {
+        let segment = self.segments[self.position(value)];
+        segment.image.saturating_add(value - segment.start)
+    }
+
+    /// `self` followed by `next`: every segment of `self` is split where its
+    /// image crosses a breakpoint of `next`.
+    pub fn then(&self, next: &Piecewise) -> Piecewise {
+        let mut segments: Vec<Segment> = Vec::new();
+
+        for (pos, segment) in self.segments.iter().enumerate() {
+            let len = self.end(pos) - segment.start as u128;
+            let image_end = (segment.image as u128 + len).min(END);
+
+            segments.push(Segment {
+                start: segment.start,
+                image: next.apply(segment.image),
+            });
+            for split in &next.segments[next.position(segment.image) + 1..] {
+                if split.start as u128 >= image_end {
+                    break;
+                }
+                segments.push(Segment {
+                    start: segment.start + (split.start - segment.image),
+                    image: split.image,
+                });
+            }
+        }
+        Piecewise::coalesce(segments)
+    }
+
+    /// Every value that maps to `value`, in increasing order. There may be
+    /// none, or several where the pieces' images overlap.
+    pub fn preimage(&self, value: u64) -> Vec<u64> {
+        self.segments
+            .iter()
+            .enumerate()
+            .filter(|&(pos, segment)| {
+                let len = self.end(pos) - segment.start as u128;
+                segment.image <= value && ((value - segment.image) as u128) < len
+            })
+            .map(|(_, segment)| segment.start + (value - segment.image))
+            .collect()
+    }
+
+    /// The smallest value `values` maps to. Each piece is increasing, so
+    /// only the first value of `values` in each piece needs looking at.
+    pub fn min_over(&self, values: &IntervalSet) -> Option<u64> {
+        values
+            .ranges()
+            .iter()
+            .flat_map(|range| {
+                let start = range.start as u64;
+                self.segments[self.position(start)..]
+                    .iter()
+                    .take_while(|segment| (segment.start as u128) < range.end)
+                    .map(move |segment| self.apply(segment.start.max(start)))
+            })
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compose::*;
+    use crate::testing::{next_random, SPAN};
+
+    fn lookup(rows: &[(u64, u64, u64)], value: u64) -> u64 {
+        rows.iter()
+            .find(|&&(_, source, len)| (source..source + len).contains(&value))
+            .map_or(value, |&(destination, source, _)| {
+                destination + (value - source)
+            })
+    }
+
+    #[test]
+    fn composition_matches_stage_by_stage() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+
+        for _ in 0..500 {
+            let maps: Vec<Vec<(u64, u64, u64)>> = (0..3)
+                .map(|_| {
+                    (0..next_random(&mut state) % 4)
+                        .map(|_| {
+                            let destination = next_random(&mut state) % SPAN;
+                            let source = next_random(&mut state) % SPAN;
+                            (destination, source, next_random(&mut state) % 16)
+                        })
+                        .collect()
+                })
+                .collect();
+            let composed = maps.iter().fold(Piecewise::identity(), |f, rows| {
+                f.then(&Piecewise::from_rows(rows))
+            });
+
+            for seed in 0..SPAN * 4 {
+                let location = maps.iter().fold(seed, |value, rows| lookup(rows, value));
+                assert_eq!(composed.apply(seed), location, "{maps:?}");
+                assert!(composed.preimage(location).contains(&seed));
+            }
+            let seeds: IntervalSet = [3..20, 40..41].into_iter().collect();
+            assert_eq!(
+                composed.min_over(&seeds),
+                (3..20).chain(40..41).map(|seed| composed.apply(seed)).min()
+            );
+        }
+    }
+
+    #[test]
+    fn covers_the_whole_domain() {
+        let f = Piecewise::from_rows(&[(0, u64::MAX - 1, 1), (5, 0, 2)]);
+
+        assert_eq!(f.apply(u64::MAX), u64::MAX);
+        assert_eq!(f.apply(u64::MAX - 1), 0);
+        assert_eq!(f.preimage(0), [u64::MAX - 1]);
+        assert_eq!(f.preimage(5), [0, 5]);
+        assert_eq!(f.preimage(1), Vec::<u64>::new());
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::almanac::Map;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    UnknownCategory(String),
+    NoRoute { from: String, to: String },
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouteError::UnknownCategory(category) => {
+                write!(f, "no map starts or ends at {category:?}")
+            }
+            RouteError::NoRoute { from, to } => {
+                write!(f, "no chain of maps leads from {from:?} to {to:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+/// The shortest chain of maps leading from category `from` to category `to`,
+/// following each map from its source to its destination category. Sections
+/// may come in any order; an empty chain takes a category to itself.
+pub fn route<'a>(maps: &'a [Map], from: &str, to: &str) -> Result<Vec<&'a Map>, RouteError> {
+    for category in [from, to] {
+        let known = maps
+            .iter()
+            .any(|map| map.source == category || map.destination == category);
+        if !known {
+            return Err(RouteError::UnknownCategory(category.to_string()));
+        }
+    }
+
+    // Breadth first from `from`, remembering the map each category was
+    // first reached through.
+    let mut reached_by: HashMap<&str, Option<&Map>> = HashMap::from([(from, None)]);
+    let mut queue: VecDeque<&str> = VecDeque::from([from]);
+    while let Some(category) = queue.pop_front() {
+        for map in maps.iter().filter(|map| map.source == category) {
+            if !reached_by.contains_key(map.destination.as_str()) {
+                reached_by.insert(&map.destination, Some(map));
+                queue.push_back(&map.destination);
+            }
+        }
+    }
+
+    let mut chain: Vec<&Map> = Vec::new();
+    let mut category = to;
+    loop {
+        match reached_by.get(category) {
+            None => {
+                return Err(RouteError::NoRoute {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                })
+            }
+            Some(None) => break,
+            Some(Some(map)) => {
+                chain.push(map);
+                category = &map.source;
+            }
+        }
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::almanac::{self, Almanac};
+    use crate::graph::*;
+
+    /// The sample with its sections in reverse order.
+    fn shuffled_sample() -> Almanac {
+        let sample = include_str!("../input/problem_one_sample.txt");
+        let mut sections: Vec<&str> = sample.trim_end().split("\n\n").collect();
+        sections[1..].reverse();
+        almanac::parse(&sections.join("\n\n"))
+    }
+
+    fn categories(route: &[&Map]) -> Vec<String> {
+        route
+            .iter()
+            .map(|map| format!("{}-{}", map.source, map.destination))
+            .collect()
+    }
+
+    #[test]
+    fn routes_through_reordered_sections() {
+        let sample = almanac::parse(include_str!("../input/problem_one_sample.txt"));
+        let shuffled = shuffled_sample();
+
+        assert_eq!(
+            categories(&shuffled.route("soil", "humidity").unwrap()),
+            [
+                "soil-fertilizer",
+                "fertilizer-water",
+                "water-light",
+                "light-temperature",
+                "temperature-humidity"
+            ]
+        );
+        assert_eq!(shuffled.composed(), sample.composed());
+        assert_eq!(shuffled.locations(), sample.locations());
+        assert_eq!(shuffled.mapping("soil", "soil").unwrap().apply(7), 7);
+        // Seed 79 is soil 81, which is humidity 78 in the puzzle.
+        assert_eq!(shuffled.mapping("soil", "humidity").unwrap().apply(81), 78);
+    }
+
+    #[test]
+    fn reports_missing_routes() {
+        let almanac = shuffled_sample();
+
+        assert_eq!(
+            almanac.route("humidity", "soil").unwrap_err(),
+            RouteError::NoRoute {
+                from: "humidity".to_string(),
+                to: "soil".to_string()
+            }
+        );
+        assert_eq!(
+            almanac.route("seed", "weather").unwrap_err(),
+            RouteError::UnknownCategory("weather".to_string())
+        );
+    }
+}
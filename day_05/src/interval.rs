@@ -0,0 +1,231 @@
+use std::ops::Range;
+
+/// Just past the largest `u64`, where a range holding `u64::MAX` ends.
+pub const END: u128 = 1 << 64;
+
+/// A set of `u64` values stored as sorted, disjoint, non-adjacent half-open
+/// ranges. Bounds are `u128` so that a range can end at [`END`]. Every
+/// operation returns a normalised set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<Range<u128>>,
+}
+
+/// Values in `source` move to `destination + (value - source.start)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece {
+    pub source: Range<u128>,
+    pub destination: u64,
+}
+
+impl Piece {
+    /// Saturates where a malformed piece would run past `u64::MAX`.
+    pub fn apply(&self, value: u64) -> u64 {
+        self.destination
+            .saturating_add((value as u128 - self.source.start) as u64)
+    }
+
+    fn apply_range(&self, range: &Range<u128>) -> Range<u128> {
+        let start = self.apply(range.start as u64) as u128;
+        start..(start + (range.end - range.start)).min(END)
+    }
+}
+
+impl FromIterator<Range<u128>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<u128>>>(ranges: I) -> IntervalSet {
+        let mut ranges: Vec<Range<u128>> = ranges.into_iter().filter(|r| r.start < r.end).collect();
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<u128>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+}
+
+impl IntervalSet {
+    pub fn ranges(&self) -> &[Range<u128>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.ranges.iter().chain(&other.ranges).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges: Vec<Range<u128>> = Vec::new();
+        let (mut left, mut right) = (0, 0);
+
+        while left < self.ranges.len() && right < other.ranges.len() {
+            let (a, b) = (&self.ranges[left], &other.ranges[right]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if overlap.start < overlap.end {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                left += 1;
+            } else {
+                right += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges: Vec<Range<u128>> = Vec::new();
+        let mut cut = other.ranges.iter().peekable();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+            while let Some(hole) = cut.peek() {
+                if hole.end <= start {
+                    cut.next();
+                    continue;
+                }
+                if hole.start >= range.end {
+                    break;
+                }
+                if hole.start > start {
+                    ranges.push(start..hole.start);
+                }
+                start = hole.end;
+                if hole.end > range.end {
+                    break;
+                }
+                cut.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// Moves every value through the first of `pieces` whose source holds
+    /// it. Values no piece holds are left where they are.
+    pub fn apply(&self, pieces: &[Piece]) -> IntervalSet {
+        let mut left = self.clone();
+        let mut moved: Vec<Range<u128>> = Vec::new();
+
+        for piece in pieces {
+            if left.is_empty() {
+                break;
+            }
+            let source: IntervalSet = [piece.source.clone()].into_iter().collect();
+            moved.extend(
+                left.intersection(&source)
+                    .ranges
+                    .iter()
+                    .map(|range| piece.apply_range(range)),
+            );
+            left = left.difference(&source);
+        }
+        moved.into_iter().collect::<IntervalSet>().union(&left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval::*;
+    use crate::testing::{next_random, SPAN};
+
+    fn random_range(state: &mut u64) -> Range<u128> {
+        let start = (next_random(state) % SPAN) as u128;
+        start..start + (next_random(state) % 12) as u128
+    }
+
+    fn random_set(state: &mut u64) -> IntervalSet {
+        (0..next_random(state) % 5)
+            .map(|_| random_range(state))
+            .collect()
+    }
+
+    fn values(set: &IntervalSet) -> Vec<u64> {
+        set.ranges
+            .iter()
+            .flat_map(|range| range.clone())
+            .map(|value| value as u64)
+            .collect()
+    }
+
+    fn brute(keep: impl Fn(u64) -> bool) -> Vec<u64> {
+        (0..SPAN * 2).filter(|&value| keep(value)).collect()
+    }
+
+    fn contains(set: &IntervalSet, value: u64) -> bool {
+        set.ranges
+            .iter()
+            .any(|range| range.contains(&(value as u128)))
+    }
+
+    fn is_normal(set: &IntervalSet) -> bool {
+        set.ranges.iter().all(|range| range.start < range.end)
+            && set
+                .ranges
+                .windows(2)
+                .all(|pair| pair[0].end < pair[1].start)
+    }
+
+    #[test]
+    fn set_operations_match_brute_force() {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+
+        for _ in 0..2_000 {
+            let (a, b) = (random_set(&mut state), random_set(&mut state));
+
+            for (set, expected) in [
+                (a.union(&b), brute(|v| contains(&a, v) || contains(&b, v))),
+                (
+                    a.intersection(&b),
+                    brute(|v| contains(&a, v) && contains(&b, v)),
+                ),
+                (
+                    a.difference(&b),
+                    brute(|v| contains(&a, v) && !contains(&b, v)),
+                ),
+            ] {
+                assert!(is_normal(&set), "{set:?}");
+                assert_eq!(values(&set), expected, "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn apply_matches_brute_force() {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+        for _ in 0..2_000 {
+            let set = random_set(&mut state);
+            let pieces: Vec<Piece> = (0..next_random(&mut state) % 4)
+                .map(|_| Piece {
+                    source: random_range(&mut state),
+                    destination: next_random(&mut state) % SPAN,
+                })
+                .collect();
+
+            let mut expected: Vec<u64> = values(&set)
+                .into_iter()
+                .map(|value| {
+                    pieces
+                        .iter()
+                        .find(|piece| piece.source.contains(&(value as u128)))
+                        .map_or(value, |piece| piece.apply(value))
+                })
+                .collect();
+            expected.sort_unstable();
+            expected.dedup();
+
+            let mapped = set.apply(&pieces);
+            assert!(is_normal(&mapped), "{mapped:?}");
+            assert_eq!(values(&mapped), expected, "{set:?} {pieces:?}");
+        }
+    }
+}
@@ -1,16 +1,158 @@
+mod almanac;
+mod compose;
+mod graph;
+mod interval;
 mod problem_one;
 mod problem_two;
+#[cfg(test)]
+mod testing;
+mod validate;
+
+use std::fmt::Display;
+use std::fs;
+use std::process;
+
+use validate::SeedMode;
+
+const USAGE: &str = "usage: day_05 [PATH]
+       day_05 [--from CATEGORY] [--to CATEGORY] --route|--table|--seed-for VALUE [PATH]
+       day_05 --locations [PATH]
+       day_05 --check single|ranges [PATH]";
+
+struct Args {
+    path: Option<String>,
+    from: String,
+    to: String,
+    route: bool,
+    table: bool,
+    seed_for: Option<u64>,
+    locations: bool,
+    check: Option<SeedMode>,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut args = Args {
+        path: None,
+        from: "seed".to_string(),
+        to: "location".to_string(),
+        route: false,
+        table: false,
+        seed_for: None,
+        locations: false,
+        check: None,
+    };
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--from" => args.from = iter.next()?,
+            "--to" => args.to = iter.next()?,
+            "--route" => args.route = true,
+            "--table" => args.table = true,
+            "--seed-for" => args.seed_for = Some(iter.next()?.parse().ok()?),
+            "--locations" => args.locations = true,
+            "--check" => {
+                args.check = match iter.next()?.as_str() {
+                    "single" => Some(SeedMode::Single),
+                    "ranges" => Some(SeedMode::Ranges),
+                    _ => return None,
+                }
+            }
+            _ if args.path.is_none() && !arg.starts_with("--") => args.path = Some(arg),
+            _ => return None,
+        }
+    }
+    Some(args)
+}
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("day_05: {err}");
+    process::exit(1);
+}
 
 fn main() {
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    };
+
+    let input = args
+        .path
+        .as_ref()
+        .map(|path| fs::read_to_string(path).unwrap_or_else(|err| fail(err)));
+
+    if let Some(mode) = args.check {
+        let file = input
+            .as_deref()
+            .unwrap_or(include_str!("../input/problem_one_input.txt"));
+        let diagnostics = validate::validate(&almanac::parse(file), mode);
+        for diagnostic in &diagnostics {
+            println!("{diagnostic}");
+        }
+        if !diagnostics.is_empty() {
+            process::exit(1);
+        }
+        return;
+    }
+    if args.route || args.table || args.seed_for.is_some() || args.locations {
+        let file = input
+            .as_deref()
+            .unwrap_or(include_str!("../input/problem_one_input.txt"));
+        let almanac = almanac::parse(file);
+
+        let (from, to) = (args.from.as_str(), args.to.as_str());
+
+        if args.route {
+            let route = almanac.route(from, to).unwrap_or_else(|err| fail(err));
+            let categories: Vec<&str> = std::iter::once(from)
+                .chain(route.iter().map(|map| map.destination.as_str()))
+                .collect();
+            println!("{}", categories.join(" -> "));
+        }
+        if args.table {
+            let mapping = almanac.mapping(from, to).unwrap_or_else(|err| fail(err));
+            println!("{from:>20}  {to:>20}");
+            for segment in mapping.segments() {
+                println!("{:>20}  {:>20}", segment.start, segment.image);
+            }
+        }
+        if let Some(value) = args.seed_for {
+            let mapping = almanac.mapping(from, to).unwrap_or_else(|err| fail(err));
+            let sources: Vec<String> = mapping.preimage(value).iter().map(u64::to_string).collect();
+            println!("{to} {value}: {from} [{}]", sources.join(", "));
+        }
+        if args.locations {
+            let locations = almanac.locations().unwrap_or_else(|err| fail(err));
+            for range in locations.ranges() {
+                println!("{}..{}", range.start, range.end);
+            }
+        }
+        return;
+    }
+
     //let file = include_str!("../input/problem_one_input.txt");
-    let file = include_str!("../input/problem_one_sample.txt");
+    let file = input
+        .as_deref()
+        .unwrap_or(include_str!("../input/problem_one_sample.txt"));
 
-    println!("{:}", problem_one::problem_one_sol(file));
+    println!(
+        "{:}",
+        problem_one::problem_one_sol(file)
+            .unwrap_or_else(|err| fail(err))
+            .unwrap_or_else(|| fail("the almanac lists no seeds"))
+    );
 
     println!("===========");
 
-    let file2 = include_str!("../input/problem_one_input.txt");
+    let file2 = input
+        .as_deref()
+        .unwrap_or(include_str!("../input/problem_one_input.txt"));
     //let file2 = include_str!("../input/problem_one_sample.txt");
 
-    println!("{:}", problem_two::problem_one_sol(file2));
+    println!(
+        "{:}",
+        problem_two::problem_one_sol(file2)
+            .unwrap_or_else(|err| fail(err))
+            .unwrap_or_else(|| fail("the almanac lists no seed ranges"))
+    );
 }
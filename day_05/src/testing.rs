@@ -0,0 +1,10 @@
+/// How far apart the random test values are spread.
+pub const SPAN: u64 = 64;
+
+/// xorshift64, so failures reproduce.
+pub fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
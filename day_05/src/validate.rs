@@ -0,0 +1,270 @@
+use std::fmt;
+
+use crate::almanac::{Almanac, Map};
+use crate::interval::END;
+
+/// How the `seeds:` line is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedMode {
+    Single,
+    Ranges,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The source range overlaps the one on line `line`, the first row that
+    /// does. That row wins the lookup wherever the two overlap.
+    Overlap {
+        line: usize,
+    },
+    /// The source range is the same as the one on line `line`.
+    Duplicate {
+        line: usize,
+    },
+    /// `source + length` or `destination + length` is past `u64::MAX`.
+    Wraps,
+    EmptyMap,
+    /// Range mode needs seeds in `start length` pairs.
+    OddSeedCount(usize),
+    /// The header does not read `<source>-to-<destination> map:`.
+    BadHeader,
+    /// The map on line `line` already goes between the same categories.
+    DuplicateMap {
+        line: usize,
+    },
+    /// The row is not `<destination> <source> <length>`.
+    BadRow,
+    /// A token of the `seeds:` line is not a number.
+    BadSeed(String),
+}
+
+/// One problem found in an almanac, with the header of the section it is
+/// in and the line it is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub header: String,
+    pub line_number: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {} ({}): ", self.line_number, self.header)?;
+        match &self.problem {
+            Problem::Overlap { line } => {
+                write!(f, "source range overlaps line {line}, which wins")
+            }
+            Problem::Duplicate { line } => write!(f, "source range repeats line {line}"),
+            Problem::Wraps => write!(f, "range runs past {}", u64::MAX),
+            Problem::EmptyMap => write!(f, "map has no ranges"),
+            Problem::OddSeedCount(count) => {
+                write!(f, "{count} seeds cannot be read as start/length pairs")
+            }
+            Problem::BadHeader => write!(f, "expected `<source>-to-<destination> map:`"),
+            Problem::DuplicateMap { line } => {
+                write!(f, "line {line} maps between the same categories")
+            }
+            Problem::BadRow => write!(f, "expected `<destination> <source> <length>`"),
+            Problem::BadSeed(seed) => write!(f, "seed {seed:?} is not a number"),
+        }
+    }
+}
+
+fn validate_map(map: &Map, diagnostics: &mut Vec<Diagnostic>) {
+    let report = |line_number: usize, problem: Problem| Diagnostic {
+        header: map.header.clone(),
+        line_number,
+        problem,
+    };
+    for &line in &map.bad_rows {
+        diagnostics.push(report(line, Problem::BadRow));
+    }
+    if map.rows.is_empty() {
+        diagnostics.push(report(map.header_line, Problem::EmptyMap));
+        return;
+    }
+
+    // Source ranges with their line numbers, in file order. Each row is
+    // checked against the rows before it, which take precedence in a lookup.
+    let mut rows: Vec<(u128, u128, usize)> = Vec::new();
+    for (&(destination, source, len), &line) in map.rows.iter().zip(&map.row_lines) {
+        if source.checked_add(len).is_none() || destination.checked_add(len).is_none() {
+            diagnostics.push(report(line, Problem::Wraps));
+        }
+        if len > 0 {
+            let start = source as u128;
+            rows.push((start, (start + len as u128).min(END), line));
+        }
+    }
+
+    for (pos, &(start, end, line)) in rows.iter().enumerate() {
+        let earlier = &rows[..pos];
+        let duplicate = earlier
+            .iter()
+            .find(|&&(other_start, other_end, _)| (other_start, other_end) == (start, end));
+        let overlap = earlier
+            .iter()
+            .find(|&&(other_start, other_end, _)| start < other_end && other_start < end);
+
+        if let Some(&(_, _, other)) = duplicate {
+            diagnostics.push(report(line, Problem::Duplicate { line: other }));
+        } else if let Some(&(_, _, other)) = overlap {
+            diagnostics.push(report(line, Problem::Overlap { line: other }));
+        }
+    }
+}
+
+/// Every problem in `almanac`, by line number.
+pub fn validate(almanac: &Almanac, mode: SeedMode) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    for seed in &almanac.bad_seeds {
+        diagnostics.push(Diagnostic {
+            header: "seeds:".to_string(),
+            line_number: almanac.seeds_line,
+            problem: Problem::BadSeed(seed.clone()),
+        });
+    }
+    if mode == SeedMode::Ranges && almanac.seeds.len() % 2 == 1 {
+        diagnostics.push(Diagnostic {
+            header: "seeds:".to_string(),
+            line_number: almanac.seeds_line,
+            problem: Problem::OddSeedCount(almanac.seeds.len()),
+        });
+    }
+    for (pos, map) in almanac.maps.iter().enumerate() {
+        let report = |problem: Problem| Diagnostic {
+            header: map.header.clone(),
+            line_number: map.header_line,
+            problem,
+        };
+        if map.source.is_empty() {
+            diagnostics.push(report(Problem::BadHeader));
+        } else if let Some(earlier) = almanac.maps[..pos].iter().find(|earlier| {
+            (&earlier.source, &earlier.destination) == (&map.source, &map.destination)
+        }) {
+            diagnostics.push(report(Problem::DuplicateMap {
+                line: earlier.header_line,
+            }));
+        }
+        validate_map(map, &mut diagnostics);
+    }
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line_number);
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::almanac;
+    use crate::validate::*;
+
+    #[test]
+    fn sample_is_clean() {
+        let almanac = almanac::parse(include_str!("../input/problem_one_sample.txt"));
+        assert_eq!(validate(&almanac, SeedMode::Ranges), []);
+    }
+
+    #[test]
+    fn reports_each_problem_with_its_map() {
+        let almanac = almanac::parse(
+            "seeds: 79 14 55
+
+seed-to-soil map:
+50 98 2
+52 50 48
+0 60 5
+1 98 2
+
+soil-to-fertilizer map:
+
+fertilizer-to-water map:
+0 18446744073709551610 10
+
+seed-to-soil map:
+1 2 3
+
+water map:
+1 2 3
+",
+        );
+        let diagnostic = |line_number: usize, header: &str, problem: Problem| Diagnostic {
+            header: header.to_string(),
+            line_number,
+            problem,
+        };
+
+        assert_eq!(
+            validate(&almanac, SeedMode::Ranges),
+            [
+                diagnostic(1, "seeds:", Problem::OddSeedCount(3)),
+                diagnostic(6, "seed-to-soil map:", Problem::Overlap { line: 5 }),
+                diagnostic(7, "seed-to-soil map:", Problem::Duplicate { line: 4 }),
+                diagnostic(9, "soil-to-fertilizer map:", Problem::EmptyMap),
+                diagnostic(12, "fertilizer-to-water map:", Problem::Wraps),
+                diagnostic(14, "seed-to-soil map:", Problem::DuplicateMap { line: 3 }),
+                diagnostic(17, "water map:", Problem::BadHeader),
+            ]
+        );
+        assert!(validate(&almanac, SeedMode::Single)
+            .iter()
+            .all(|diagnostic| diagnostic.problem != Problem::OddSeedCount(3)));
+    }
+
+    #[test]
+    fn overlaps_name_the_row_that_wins() {
+        let almanac = almanac::parse(
+            "seeds: 8
+
+seed-to-soil map:
+0 0 10
+0 5 20
+0 8 1
+",
+        );
+        let overlap = |line_number: usize, line: usize| Diagnostic {
+            header: "seed-to-soil map:".to_string(),
+            line_number,
+            problem: Problem::Overlap { line },
+        };
+
+        assert_eq!(
+            validate(&almanac, SeedMode::Single),
+            [overlap(5, 4), overlap(6, 4)]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_rows_and_seeds() {
+        let almanac = almanac::parse(
+            "seeds: 79 x14
+
+seed-to-soil map:
+50 98 2
+1 2
+52 fifty 48
+",
+        );
+
+        assert_eq!(almanac.maps[0].rows, [(50, 98, 2)]);
+        assert_eq!(
+            validate(&almanac, SeedMode::Single),
+            [
+                Diagnostic {
+                    header: "seeds:".to_string(),
+                    line_number: 1,
+                    problem: Problem::BadSeed("x14".to_string()),
+                },
+                Diagnostic {
+                    header: "seed-to-soil map:".to_string(),
+                    line_number: 5,
+                    problem: Problem::BadRow,
+                },
+                Diagnostic {
+                    header: "seed-to-soil map:".to_string(),
+                    line_number: 6,
+                    problem: Problem::BadRow,
+                },
+            ]
+        );
+    }
+}